@@ -1,5 +1,17 @@
+mod bump;
+mod changelog;
 mod check;
 mod errors;
+mod hooks;
+mod promote;
+mod report;
 mod tag;
-pub use check::check;
-pub use tag::tag;
+mod version;
+pub use bump::bump;
+pub use changelog::changelog;
+pub use check::{check, check_message, check_output, CheckedCommit, RejectedCommit};
+pub use hooks::{hooks, HooksAction};
+pub use promote::promote;
+pub use report::CheckOutput;
+pub use tag::{release_plan, tag, ReleasePlan, VersionQuery};
+pub use version::{version, VersionOutput};
@@ -0,0 +1,229 @@
+use crate::command::tag::{next_version, package_versions, VersionQuery, CALVER_UNSUPPORTED};
+use crate::config::{BumpFile, BumpFileKind, Config, Scheme};
+use crate::utils::bump::{read_version, write_version};
+use crate::utils::manifest::Manifest;
+use crate::utils::pep440::Pep440;
+use crate::utils::plan;
+use crate::utils::semver::SemVer;
+use crate::utils::version::VersionScheme;
+use crate::utils::workspace::Workspace;
+use crate::utils::SemVerChangeType;
+use log::{info, warn};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::Path;
+use toml_edit::{DocumentMut, Item};
+
+/// Writes the next version into every file listed in `bump_files`.
+///
+/// All files are read and validated before anything is written: the version
+/// each one currently holds must match the latest tag, otherwise the command
+/// fails without touching any file.
+///
+/// A `cargo` file at the root of a Cargo workspace releases every member, the
+/// path dependency requirements and `Cargo.lock` in step.
+///
+/// When `packages` are configured, the `Cargo.toml` of every package with a
+/// pending release is bumped instead, see [`bump_packages`]. `config_check`
+/// rejects `bump_files` along with `packages`.
+///
+/// # Arguments
+/// - `query`   – Selects the tags and the history to compute the version from.
+///
+/// - `dry_run` – Print the diff of every file instead of writing it.
+///
+/// - `config`  – Project-level configuration holding the `bump_files`.
+pub fn bump(query: VersionQuery, dry_run: bool, config: &Config) -> anyhow::Result<()> {
+    if config.packages.is_some() {
+        return bump_packages(&query, dry_run, config);
+    }
+    let Some(ref files) = config.bump_files else {
+        return Err(anyhow::anyhow!("no bump_files configured, nothing to bump"));
+    };
+
+    match config.scheme(None)? {
+        Scheme::SemVer => bump_files::<SemVer>(&query, files, dry_run, config),
+        Scheme::Pep440 => bump_files::<Pep440>(&query, files, dry_run, config),
+        Scheme::CalVer => Err(anyhow::anyhow!(CALVER_UNSUPPORTED)),
+    }
+}
+
+/// Parses `version` as the SemVer a Cargo manifest requires.
+fn cargo_version(version: &impl Display) -> anyhow::Result<SemVer> {
+    version
+        .to_string()
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Cargo needs SemVer versions, found {}", version))
+}
+
+/// Returns `true` if `current`, the version held by the file at `path`, is
+/// the `latest` tagged one. Without a tag there is nothing to validate.
+fn matches_latest<V: VersionScheme>(path: &str, current: &V, latest: Option<&V>) -> bool {
+    match latest {
+        Some(latest) => latest == current,
+        None => {
+            warn!(
+                "no tag found, {} version {} is not validated",
+                path, current
+            );
+            true
+        }
+    }
+}
+
+/// Reads the version of the `Cargo.toml` in `dir`, the `workspace.package`
+/// one of the repository root when the crate inherits it.
+fn manifest_version(dir: &Path) -> anyhow::Result<SemVer> {
+    let file = BumpFile {
+        path: dir.join("Cargo.toml").display().to_string(),
+        kind: BumpFileKind::Cargo,
+        pattern: None,
+    };
+    let content = std::fs::read_to_string(&file.path)
+        .map_err(|e| anyhow::anyhow!("couldn't read {}: {}", file.path, e))?;
+    let inherited = content
+        .parse::<DocumentMut>()?
+        .get("package")
+        .and_then(|x| x.get("version"))
+        .and_then(|x| x.get("workspace"))
+        .and_then(Item::as_bool)
+        .unwrap_or_default();
+    if !inherited {
+        return read_version(&content, &file);
+    }
+    let root = std::fs::read_to_string("Cargo.toml")
+        .map_err(|e| anyhow::anyhow!("couldn't read Cargo.toml: {}", e))?
+        .parse::<DocumentMut>()?;
+    let version = root
+        .get("workspace")
+        .and_then(|x| x.get("package"))
+        .and_then(|x| x.get("version"))
+        .and_then(Item::as_str)
+        .ok_or(anyhow::anyhow!(
+            "{} inherits workspace.package.version, none found",
+            file.path
+        ))?;
+    cargo_version(&version)
+}
+
+/// Writes the next version, in the versioning scheme `V`, into `files`.
+fn bump_files<V: VersionScheme>(
+    query: &VersionQuery,
+    files: &[BumpFile],
+    dry_run: bool,
+    config: &Config,
+) -> anyhow::Result<()> {
+    let next = next_version::<V>(query, config)?;
+    if next.change == SemVerChangeType::None {
+        info!("no release worthy commits found, nothing to bump");
+        return Ok(());
+    }
+    info!("bumping to {}", next.next);
+
+    // Validate every file before writing any of them.
+    let mut contents: Vec<String> = Vec::new();
+    let mut mismatches: Vec<String> = Vec::new();
+    for file in files {
+        let content = std::fs::read_to_string(&file.path)
+            .map_err(|e| anyhow::anyhow!("couldn't read {}: {}", file.path, e))?;
+        let current: V = read_version(&content, file)?;
+        if !matches_latest(&file.path, &current, next.latest.as_ref()) {
+            mismatches.push(format!("{}: found {}", file.path, current));
+        }
+        contents.push(content);
+    }
+    if !mismatches.is_empty() {
+        return Err(anyhow::anyhow!(
+            "bump files do not match the latest tag {}:\n\t{}",
+            next.pattern.format(next.latest.as_ref().unwrap()),
+            mismatches.join("\n\t")
+        ));
+    }
+
+    for (file, content) in files.iter().zip(contents) {
+        let path = Path::new(&file.path);
+        let workspace = match file.kind {
+            BumpFileKind::Cargo => Workspace::read(path.parent().unwrap_or(Path::new("")))?,
+            _ => None,
+        };
+        match workspace {
+            Some(workspace) => {
+                // Every member is released in step with the workspace
+                let version = cargo_version(&next.next)?;
+                let versions: BTreeMap<String, SemVer> = workspace
+                    .crates()?
+                    .into_iter()
+                    .map(|x| (x, version.clone()))
+                    .collect();
+                for (path, updated) in workspace.release(&versions, Some(&version))? {
+                    plan::write_file(&path, &updated, dry_run)?;
+                }
+            }
+            None => {
+                let updated = write_version(&content, file, &next.next)?;
+                plan::write_file(path, &updated, dry_run)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes the next version of every package with a pending release into its
+/// `Cargo.toml`, along with the requirements of its dependents and the
+/// `Cargo.lock` of the workspace at the repository root.
+///
+/// Only `query.package` is bumped when set. Like with `bump_files`, every
+/// manifest must hold the version of the latest tag of its package, otherwise
+/// the command fails without touching any file.
+fn bump_packages(query: &VersionQuery, dry_run: bool, config: &Config) -> anyhow::Result<()> {
+    let Some(workspace) = Workspace::read(Path::new(""))? else {
+        return Err(anyhow::anyhow!(
+            "packages are bumped through the Cargo workspace of the repository root, none found"
+        ));
+    };
+
+    let mut versions: BTreeMap<String, SemVer> = BTreeMap::new();
+    let mut mismatches: Vec<String> = Vec::new();
+    for release in package_versions(query, config)? {
+        let name = release.package.clone().unwrap_or_default();
+        let skipped = query.package.as_ref().is_some_and(|x| *x != name);
+        if !release.is_release() || skipped {
+            continue;
+        }
+        let path = &config.package(&name)?.path;
+        let Some(manifest) = Manifest::read(Path::new(path))? else {
+            warn!("no Cargo.toml found in {}, {} is not bumped", path, name);
+            continue;
+        };
+        let current = manifest_version(Path::new(path))?;
+        let latest = release.latest.as_ref().map(cargo_version).transpose()?;
+        let manifest_path = Path::new(path).join("Cargo.toml").display().to_string();
+        let matches = matches_latest(&manifest_path, &current, latest.as_ref());
+        if let (false, Some(ref latest)) = (matches, latest) {
+            mismatches.push(format!(
+                "{}: found {}, latest tag {}",
+                manifest_path,
+                current,
+                release.pattern.format(latest)
+            ));
+        }
+        info!("bumping {} to {}", name, release.next);
+        versions.insert(manifest.name, cargo_version(&release.next)?);
+    }
+    if !mismatches.is_empty() {
+        return Err(anyhow::anyhow!(
+            "package manifests do not match their latest tag:\n\t{}",
+            mismatches.join("\n\t")
+        ));
+    }
+    if versions.is_empty() {
+        info!("no release worthy commits found, nothing to bump");
+        return Ok(());
+    }
+
+    let inherited = workspace.inherited_version(&versions)?;
+    for (path, updated) in workspace.release(&versions, inherited.as_ref())? {
+        plan::write_file(&path, &updated, dry_run)?;
+    }
+    Ok(())
+}
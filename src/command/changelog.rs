@@ -0,0 +1,202 @@
+use crate::command::check::range_commits;
+use crate::command::tag::parse_tags;
+use crate::config::{Config, Scheme};
+use crate::utils::calver::CalVer;
+use crate::utils::pep440::Pep440;
+use crate::utils::semver::SemVer;
+use crate::utils::tag_pattern::TagPattern;
+use crate::utils::version::VersionScheme;
+use crate::utils::{git, plan, ConventionalCommit, SemVerChangeType};
+use std::path::Path;
+
+/// Title used for commits that are not part of any tag yet.
+const UNRELEASED: &str = "Unreleased";
+
+/// Heading of the section listing breaking changes, always rendered first.
+const BREAKING_HEADING: &str = "### ⚠ BREAKING CHANGES";
+
+/// Renders a single changelog entry.
+///
+/// # Example
+/// `- **parser:** handle empty scopes (a1b2c3d)`
+fn render_entry(commit: &ConventionalCommit) -> String {
+    match commit.scope {
+        Some(ref scope) => format!(
+            "- **{}:** {} ({})\n",
+            scope, commit.short_description, commit.short_sha
+        ),
+        None => format!("- {} ({})\n", commit.short_description, commit.short_sha),
+    }
+}
+
+/// Renders the Markdown section of one version.
+///
+/// Breaking changes are listed first, then the remaining commits are grouped
+/// by `commit_type` following the order of `config.cc_types`.
+fn render_section(title: &str, commits: &[ConventionalCommit], config: &Config) -> String {
+    let mut section = format!("## {}\n", title);
+
+    let (breaking, others): (Vec<&ConventionalCommit>, Vec<&ConventionalCommit>) = commits
+        .iter()
+        .partition(|x| x.change == SemVerChangeType::Major);
+
+    if !breaking.is_empty() {
+        section.push_str(&format!("\n{}\n\n", BREAKING_HEADING));
+        for commit in breaking {
+            section.push_str(&render_entry(commit));
+        }
+    }
+
+    for cc_type in &config.cc_types {
+        let group: Vec<&&ConventionalCommit> = others
+            .iter()
+            .filter(|x| x.commit_type == *cc_type)
+            .collect();
+        if group.is_empty() {
+            continue;
+        }
+        section.push_str(&format!("\n### {}\n\n", cc_type));
+        for commit in group {
+            section.push_str(&render_entry(commit));
+        }
+    }
+    section
+}
+
+/// Returns the tag names of `listed` matching `pattern` along with their
+/// version in the scheme `V`, oldest first.
+fn scheme_tags<V: VersionScheme>(
+    listed: &str,
+    pattern: &TagPattern,
+    ignore_prereleases: bool,
+) -> Vec<(String, String)> {
+    let mut tags: Vec<V> = parse_tags::<V>(listed, pattern)
+        .into_iter()
+        .filter(|x| !ignore_prereleases || !x.is_prerelease())
+        .collect();
+    tags.sort();
+    tags.iter()
+        .map(|x| (pattern.format(x), x.to_string()))
+        .collect()
+}
+
+/// Generates a Markdown changelog from the git history.
+///
+/// Every SemVer tag gets its own section containing the commits between it
+/// and the previous tag, newest first. Commits after the latest tag are listed
+/// under an "Unreleased" section.
+///
+/// # Arguments
+/// - `output`             – File the changelog is written to. When `None`, it is printed to stdout.
+///
+/// - `ignore_prereleases` – When `true` (or when `tag.ignore_prereleases` is set in the config), pre-release tags do not get their own section.
+///
+/// - `dry_run`            – Print the diff of `output` instead of writing it.
+///
+/// - `config`             – Project-level configuration used during commit parsing.
+pub fn changelog(
+    output: Option<String>,
+    ignore_prereleases: bool,
+    dry_run: bool,
+    config: &Config,
+) -> anyhow::Result<()> {
+    let ignore_prereleases = ignore_prereleases
+        || config
+            .tag
+            .as_ref()
+            .and_then(|x| x.ignore_prereleases)
+            .unwrap_or(false);
+
+    let pattern = TagPattern::from_config(config);
+    let listed = String::from_utf8(git::tag(&["-l"])?.stdout)?;
+    // Tag names along with their version, oldest first
+    let tags: Vec<(String, String)> = match config.scheme(None)? {
+        Scheme::CalVer => {
+            let format = config.calver_format()?;
+            let mut tags: Vec<CalVer> = listed
+                .lines()
+                .filter_map(|x| format.parse(pattern.strip(x)?))
+                .collect();
+            tags.sort();
+            tags.iter()
+                .map(|x| (pattern.format(x), x.to_string()))
+                .collect()
+        }
+        Scheme::SemVer => scheme_tags::<SemVer>(&listed, &pattern, ignore_prereleases),
+        Scheme::Pep440 => scheme_tags::<Pep440>(&listed, &pattern, ignore_prereleases),
+    };
+
+    let mut sections: Vec<String> = Vec::new();
+    let mut previous: Option<&str> = None;
+    for (tag, version) in &tags {
+        let range = match previous {
+            Some(prev) => format!("{}..{}", prev, tag),
+            None => tag.clone(),
+        };
+        let commits = range_commits(&range, config)?;
+        sections.push(render_section(version, &commits, config));
+        previous = Some(tag);
+    }
+
+    let unreleased = match previous {
+        Some(prev) => range_commits(&format!("{}..HEAD", prev), config)?,
+        None => range_commits("HEAD", config)?,
+    };
+    if !unreleased.is_empty() {
+        sections.push(render_section(UNRELEASED, &unreleased, config));
+    }
+
+    sections.reverse();
+    let rendered = format!("# Changelog\n\n{}", sections.join("\n"));
+
+    match output {
+        Some(path) => plan::write_file(Path::new(&path), &rendered, dry_run)?,
+        None => print!("{}", rendered),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render_section, BREAKING_HEADING};
+    use crate::config::Config;
+    use crate::utils::ConventionalCommit;
+
+    fn commit(msg: &str, sha: &str) -> ConventionalCommit {
+        ConventionalCommit::new(msg, &Config::default(), sha.to_owned()).unwrap()
+    }
+
+    #[test]
+    fn grouped_by_type() {
+        let commits = vec![
+            commit("fix: second fix", "ccc"),
+            commit("feat(cli): new flag", "bbb"),
+            commit("fix(parser): first fix", "aaa"),
+        ];
+        let section = render_section("1.1.0", &commits, &Config::default());
+        assert_eq!(
+            section,
+            "## 1.1.0\n\
+             \n### feat\n\n\
+             - **cli:** new flag (bbb)\n\
+             \n### fix\n\n\
+             - second fix (ccc)\n\
+             - **parser:** first fix (aaa)\n"
+        );
+    }
+
+    #[test]
+    fn breaking_first() {
+        let commits = vec![
+            commit("fix: small fix", "bbb"),
+            commit("feat!: drop old api", "aaa"),
+        ];
+        let section = render_section("2.0.0", &commits, &Config::default());
+        assert!(section.starts_with(&format!(
+            "## 2.0.0\n\n{}\n\n- drop old api (aaa)\n",
+            BREAKING_HEADING
+        )));
+        assert!(!section.contains("### feat"));
+        assert!(section.ends_with("### fix\n\n- small fix (bbb)\n"));
+    }
+}
@@ -1,15 +1,24 @@
-use crate::config::Config;
+use crate::command::report::{self, CheckOutput};
+use crate::config::{Config, Package, Severity};
+use crate::utils::errors::ConventionalCommitParseError;
+use crate::utils::lint::{lint, Violation};
 use crate::utils::{git, ConventionalCommit};
 use colored::Colorize;
-use log::{debug, info};
+use log::{debug, info, warn};
 use prettytable::{color, Attr, Cell, Row, Table};
 
 /// Visual separator used to delimit the check output sections in the terminal.
 const SEPARATOR: &str = "################################################";
 
+/// Comment character of commit messages when `core.commentChar` is not set.
+const DEFAULT_COMMENT_CHAR: char = '#';
+
+/// Scissors line of `git commit --verbose`, everything below it is dropped.
+const SCISSORS: &str = "------------------------ >8 ------------------------";
+
 /// Default character width before wrapping long text in table cells.
 /// Used as a fallback when `return_n` is not provided.
-const DEFAULT_WRAP_WIDTH: usize = 40;
+pub(crate) const DEFAULT_WRAP_WIDTH: usize = 40;
 
 /// Inserts a newline character every `n_char` characters to prevent long strings
 /// from overflowing table cells in the terminal output.
@@ -72,6 +81,205 @@ fn build_error_row(sha: &str, commit_msg: &str, wrap_width: usize, error: &str)
     ])
 }
 
+/// A commit of the history that is not a Conventional Commit.
+#[derive(Debug, PartialEq, Clone)]
+pub struct RejectedCommit {
+    /// Short SHA of the commit.
+    pub sha: String,
+    /// Raw commit message.
+    pub message: String,
+    /// Reason the message was rejected.
+    pub error: ConventionalCommitParseError,
+}
+
+/// Builds the error [`Table`] listing the `rejected` commits, wrapped at
+/// `wrap_width` characters. The header row is always present.
+pub(crate) fn error_table(rejected: &[RejectedCommit], wrap_width: usize) -> Table {
+    let mut table = build_error_table_header();
+    for commit in rejected {
+        table.add_row(build_error_row(
+            &commit.sha,
+            &commit.message,
+            wrap_width,
+            &commit.error.to_string(),
+        ));
+    }
+    table
+}
+
+/// Builds the `git log` arguments producing a parseable, decoration-free log
+/// for the optional list of refs in `name`, limited to the commits touching
+/// `path` when set.
+fn log_args<'a>(name: Option<&'a [String]>, path: Option<&'a str>) -> Vec<&'a str> {
+    let mut format_args: Vec<&str> = vec!["--no-decorate", "--format=\"%h%n%B\""];
+    if let Some(branches) = name {
+        format_args.extend(branches.iter().map(String::as_str));
+    }
+    if let Some(path) = path {
+        format_args.extend(["--", path]);
+    }
+    format_args
+}
+
+/// Runs `git log` over the refs in `name` (the current branch when `None`) and
+/// returns every commit as a `(short_sha, message)` pair, newest first.
+/// Only the commits touching `path` are returned when it is set.
+///
+/// Commits are returned unparsed so callers can decide how to handle messages
+/// that are not Conventional Commits.
+///
+/// # Errors
+/// - Returns [`Err`] if `git log` fails or its output contains invalid UTF-8.
+pub(crate) fn log_commits(
+    name: Option<&[String]>,
+    path: Option<&str>,
+) -> anyhow::Result<Vec<(String, String)>> {
+    let output = git::log(&log_args(name, path))?;
+
+    // Decode the raw bytes from git into a UTF-8 string for parsing.
+    let raw = String::from_utf8(output.stdout)
+        .map_err(|e| anyhow::anyhow!("git output was not valid UTF-8: {}", e))?;
+    debug!("{:#?}", raw);
+
+    let mut commits = Vec::new();
+
+    // Each commit block is delimited by `"\n` (the closing quote of the format string).
+    // Split on that boundary and parse each block into a (sha, message) pair.
+    for row in raw.split("\"\n") {
+        // An empty row signals the end of output; stop processing.
+        if row.is_empty() {
+            break;
+        }
+
+        // The format `%h%n%B` yields:  <short-sha>\n<body>
+        // A missing newline means the block is malformed; skip it with a debug note.
+        let Some((raw_sha, commit_msg)) = row.split_once('\n') else {
+            debug!("skipping malformed row: {:?}", row);
+            continue;
+        };
+
+        // Strip the leading `"` that git appends due to the quoted format string.
+        commits.push((raw_sha.replace('"', ""), commit_msg.to_owned()));
+    }
+    Ok(commits)
+}
+
+/// Parses every commit of the `git log` range into [`ConventionalCommit`]s,
+/// skipping the ones that are not compliant.
+pub(crate) fn range_commits(
+    range: &str,
+    config: &Config,
+) -> anyhow::Result<Vec<ConventionalCommit>> {
+    let commits = log_commits(Some(&[range.to_owned()]), None)?
+        .into_iter()
+        .filter_map(
+            |(sha, msg)| match ConventionalCommit::new(&msg, config, sha.clone()) {
+                Ok(commit) => Some(commit),
+                Err(err) => {
+                    debug!("skipping non compliant commit {}: {}", sha, err);
+                    None
+                }
+            },
+        )
+        .collect();
+    Ok(commits)
+}
+
+/// A commit of the history examined by [`check`], parsed or rejected.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CheckedCommit {
+    /// Short SHA of the commit.
+    pub sha: String,
+    /// Raw commit message.
+    pub message: String,
+    /// The parsed commit, or the reason it is not CC compliant.
+    pub result: Result<ConventionalCommit, ConventionalCommitParseError>,
+    /// Lint rules of `config.rules` broken by the message.
+    pub violations: Vec<Violation>,
+}
+
+impl CheckedCommit {
+    /// Parses `message` and checks it against the lint rules of `config`.
+    pub fn new(sha: String, message: String, config: &Config) -> Self {
+        let result = ConventionalCommit::new(&message, config, sha.clone());
+        let violations = match config.rules {
+            Some(ref rules) => lint(&message, result.as_ref().ok(), rules),
+            None => Vec::new(),
+        };
+        Self {
+            sha,
+            message,
+            result,
+            violations,
+        }
+    }
+
+    /// Returns `true` if the commit is CC compliant and breaks no rule of
+    /// `error` severity.
+    pub fn is_valid(&self) -> bool {
+        self.result.is_ok() && !self.violations.iter().any(|x| x.severity == Severity::Error)
+    }
+
+    /// Returns every problem of the commit, the parse error first, then the
+    /// violations of any severity.
+    pub fn problems(&self) -> Vec<String> {
+        let error = self.result.as_ref().err().map(ToString::to_string);
+        error
+            .into_iter()
+            .chain(self.violations.iter().map(ToString::to_string))
+            .collect()
+    }
+}
+
+/// Parses every commit of `git log` over the refs in `name`, newest first.
+/// When `package` is set, only its commits are examined (see [`check`]).
+pub(crate) fn examine_history(
+    name: Option<&[String]>,
+    package: Option<&Package>,
+    config: &Config,
+) -> anyhow::Result<Vec<CheckedCommit>> {
+    let mut examined = Vec::new();
+    for (sha, message) in log_commits(name, package.map(|x| x.path.as_str()))? {
+        let checked = CheckedCommit::new(sha, message, config);
+        // Commits scoped to other packages are not part of this one.
+        if let Ok(ref commit) = checked.result {
+            if package.is_some_and(|x| !x.has_scope(commit.scope.as_deref())) {
+                debug!("skipping {}, its scope is not part of the package", checked.sha);
+                continue;
+            }
+        }
+        examined.push(checked);
+    }
+    Ok(examined)
+}
+
+/// Parses the commits of `git log` over the refs in `name` without printing
+/// anything, unlike [`check`]. When `package` is set, only its commits are
+/// considered (see [`check`]).
+///
+/// # Returns
+/// `Ok((commits, rejected))`, where `commits` are the valid
+/// [`ConventionalCommit`]s and `rejected` the non-compliant ones.
+pub(crate) fn parse_history(
+    name: Option<&[String]>,
+    package: Option<&Package>,
+    config: &Config,
+) -> anyhow::Result<(Vec<ConventionalCommit>, Vec<RejectedCommit>)> {
+    let mut commits: Vec<ConventionalCommit> = Vec::new();
+    let mut rejected: Vec<RejectedCommit> = Vec::new();
+    for examined in examine_history(name, package, config)? {
+        match examined.result {
+            Ok(commit) => commits.push(commit),
+            Err(error) => rejected.push(RejectedCommit {
+                sha: examined.sha,
+                message: examined.message,
+                error,
+            }),
+        }
+    }
+    Ok((commits, rejected))
+}
+
 /// Checks whether recent git commits conform to the Conventional Commits specification.
 ///
 /// Runs `git log` with a machine-readable format, parses each commit, and validates
@@ -81,6 +289,8 @@ fn build_error_row(sha: &str, commit_msg: &str, wrap_width: usize, error: &str)
 /// # Arguments
 /// - `name`        – Optional list of branch names/refs to pass to `git log`. When `None`, the current branch's full history is checked.
 ///
+/// - `package`     – Optional monorepo package to check. Only the commits touching its `path`, and having one of its `scopes` when set, are checked.
+///
 /// - `return_n`    – Optional override for the cell wrap width (reuses the param name from the CLI; defaults to [`DEFAULT_WRAP_WIDTH`] when `None`).
 ///
 /// - `config`      – Project-level configuration used during commit parsing.
@@ -98,6 +308,7 @@ fn build_error_row(sha: &str, commit_msg: &str, wrap_width: usize, error: &str)
 /// - Returns [`Err`] if the git output contains invalid UTF-8.
 pub fn check(
     name: Option<Vec<String>>,
+    package: Option<&Package>,
     return_n: Option<usize>,
     config: &Config,
     raise_error: bool,
@@ -107,75 +318,50 @@ pub fn check(
     // Use `return_n` as the cell wrap width, falling back to the default constant.
     let wrap_width = return_n.unwrap_or(DEFAULT_WRAP_WIDTH);
 
-    // `format_args` produces a parseable, decoration-free log for processing.
-    // `display_args` produces a human-readable graph for terminal output.
-    let mut format_args: Vec<&str> = vec!["--no-decorate", "--format=\"%h%n%B\""];
+    // Print the decorated graph first so the user can see the context.
     let mut display_args: Vec<&str> = vec!["--oneline", "--decorate", "--graph"];
+    if let Some(ref branches) = name {
+        display_args.extend(branches.iter().map(String::as_str));
+    }
+    if let Some(package) = package {
+        display_args.extend(["--", package.path.as_str()]);
+    }
+    print!(
+        "LOG:\n{}",
+        String::from_utf8(git::log(&display_args)?.stdout)?
+    );
 
-    // Run `git log` with branch filters if provided, otherwise use the default ref.
-    // In both cases, print the decorated graph first so the user can see the context.
-    let (output, debug_cmd) = match name {
-        Some(ref branches) => {
-            let branch_args: Vec<&str> = branches.iter().map(String::as_str).collect();
-            display_args.extend(&branch_args);
-            format_args.extend(&branch_args);
-
-            print!("LOG:\n{}", String::from_utf8(git::log(&display_args)?.stdout)?);
-            let cmd_str = format_args.join(" ");
-            (git::log(&format_args)?, cmd_str)
-        }
-        None => {
-            print!("LOG:\n{}", String::from_utf8(git::log(&display_args)?.stdout)?);
-            let cmd_str = format_args.join(" ");
-            (git::log(&format_args)?, cmd_str)
-        }
-    };
+    let path = package.map(|x| x.path.as_str());
+    let debug_cmd = log_args(name.as_deref(), path).join(" ");
 
-    // Decode the raw bytes from git into a UTF-8 string for parsing.
-    let raw = String::from_utf8(output.stdout)
-        .map_err(|e| anyhow::anyhow!("git output was not valid UTF-8: {}", e))?;
-    debug!("{:#?}", raw);
+    let examined = examine_history(name.as_deref(), package, config)?;
+    let total = examined.len();
+    let bad = examined.iter().filter(|x| !x.is_valid()).count();
 
-    let mut commits: Vec<ConventionalCommit> = Vec::new();
+    // Every commit with a problem is listed, the ones only breaking `warn`
+    // rules included.
     let mut err_table = build_error_table_header();
-
-    // Each commit block is delimited by `"\n` (the closing quote of the format string).
-    // Split on that boundary and parse each block into a (sha, message) pair.
-    for row in raw.split("\"\n") {
-        // An empty row signals the end of output; stop processing.
-        if row.is_empty() {
-            break;
-        }
-
-        // The format `%h%n%B` yields:  <short-sha>\n<body>
-        // A missing newline means the block is malformed; skip it with a debug note.
-        let Some((raw_sha, commit_msg)) = row.split_once('\n') else {
-            debug!("skipping malformed row: {:?}", row);
-            continue;
-        };
-
-        // Strip the leading `"` that git appends due to the quoted format string.
-        let sha = raw_sha.replace('"', "");
-
-        // Attempt to parse the commit as a Conventional Commit.
-        // Valid commits are collected; invalid ones are added to the error table.
-        match ConventionalCommit::new(commit_msg, config, sha.clone()) {
-            Ok(commit) => commits.push(commit),
-            Err(err) => {
-                err_table.add_row(build_error_row(&sha, commit_msg, wrap_width, &err.to_string()));
-            }
-        }
+    for commit in examined
+        .iter()
+        .filter(|x| x.result.is_err() || !x.violations.is_empty())
+    {
+        err_table.add_row(build_error_row(
+            &commit.sha,
+            &commit.message,
+            wrap_width,
+            &commit.problems().join("\n"),
+        ));
     }
+    let commits: Vec<ConventionalCommit> =
+        examined.into_iter().filter_map(|x| x.result.ok()).collect();
 
-    // `err_table` always contains at least one row (the header), so `len() > 1`
-    // means at least one non-compliant commit was found.
-    if err_table.len() > 1 && raise_error {
+    if bad > 0 && raise_error {
         err_table.printstd();
         Err(anyhow::Error::msg(
             format!(
                 "\nCommit History not cc compliant: \nfound {} bad commits out of {}",
-                err_table.len() - 1,          // subtract the header row
-                commits.len() + err_table.len() - 1 // total commits checked
+                bad,
+                total
             )
             .red(),
         ))
@@ -189,7 +375,167 @@ pub fn check(
             .red(),
         ))
     } else {
+        // `err_table` always contains the header row, more rows are warnings.
+        if err_table.len() > 1 && raise_error {
+            err_table.printstd();
+        }
         info!("{}", format!("\n\nAll commits out of {} checked are ok", commits.len()).green());
         Ok((commits, err_table))
     }
 }
+
+/// Returns `message` cleaned up the way `git commit` does before recording
+/// it: the lines starting with `comment_char` and everything from the
+/// scissors line on are removed, as are trailing spaces, repeated blank
+/// lines and the blank lines around the message.
+fn clean_message(message: &str, comment_char: char) -> String {
+    let scissors = format!("{} {}", comment_char, SCISSORS);
+    let mut lines: Vec<&str> = Vec::new();
+    for line in message.lines() {
+        if line == scissors {
+            break;
+        }
+        if line.starts_with(comment_char) {
+            continue;
+        }
+        let line = line.trim_end();
+        if line.is_empty() && lines.last().is_none_or(|x| x.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    while lines.last().is_some_and(|x| x.is_empty()) {
+        lines.pop();
+    }
+    lines.iter().map(|x| format!("{}\n", x)).collect()
+}
+
+/// Validates a single commit `message`, e.g. the one of a `commit-msg` hook,
+/// after cleaning it up like git does (see [`clean_message`]).
+///
+/// The comment character is read from `core.commentChar`, `#` by default.
+///
+/// # Arguments
+/// - `message` – Raw commit message, as written by `git commit` or a user.
+///
+/// - `config`  – Project-level configuration used during commit parsing.
+///
+/// # Errors
+/// - Returns [`Err`] with a single line error if the message is empty, not
+///   CC compliant or breaks a rule of `error` severity.
+pub fn check_message(message: &str, config: &Config) -> anyhow::Result<()> {
+    let comment_char = git::config_value("core.commentChar")?
+        .and_then(|x| x.chars().next())
+        .filter(|x| x.is_ascii_punctuation())
+        .unwrap_or(DEFAULT_COMMENT_CHAR);
+    let message = clean_message(message, comment_char);
+    let Some(header) = message.lines().next().map(str::to_owned) else {
+        return Err(anyhow::anyhow!("empty commit message"));
+    };
+    let checked = CheckedCommit::new(String::new(), message, config);
+    for violation in &checked.violations {
+        if violation.severity == Severity::Warn {
+            warn!("{}: {}", violation.rule, violation.message);
+        }
+    }
+    if checked.is_valid() {
+        info!("{} is CC compliant", header);
+        return Ok(());
+    }
+    let errors: Vec<String> = checked
+        .result
+        .as_ref()
+        .err()
+        .map(ToString::to_string)
+        .into_iter()
+        .chain(
+            checked
+                .violations
+                .iter()
+                .filter(|x| x.severity == Severity::Error)
+                .map(ToString::to_string),
+        )
+        .collect();
+    Err(anyhow::anyhow!(
+        "{:?} is not CC compliant: {}",
+        header,
+        errors.join("; ").replace('\n', " ")
+    ))
+}
+
+/// Checks the commits like [`check`] and prints them in the `output` format.
+///
+/// The structured formats report every examined commit, parsed or rejected,
+/// along with a summary, and print nothing else to stdout.
+///
+/// # Arguments
+/// - `name`     – Optional list of branch names/refs to pass to `git log`, see [`check`].
+///
+/// - `package`  – Optional monorepo package to check, see [`check`].
+///
+/// - `return_n` – Optional cell wrap width of the `table` output.
+///
+/// - `output`   – Format of the report.
+///
+/// - `config`   – Project-level configuration used during commit parsing.
+///
+/// # Errors
+/// - Returns [`Err`] if non-compliant commits exist, after printing the report.
+/// - Returns [`Err`] if `git log` produced zero commits (likely a bad ref).
+pub fn check_output(
+    name: Option<Vec<String>>,
+    package: Option<&Package>,
+    return_n: Option<usize>,
+    output: CheckOutput,
+    config: &Config,
+) -> anyhow::Result<()> {
+    let rendered = match output {
+        CheckOutput::Table => return check(name, package, return_n, config, true).map(|_| ()),
+        CheckOutput::Json => report::json,
+        CheckOutput::Junit => report::junit,
+        CheckOutput::Sarif => report::sarif,
+    };
+
+    let examined = examine_history(name.as_deref(), package, config)?;
+    if examined.is_empty() {
+        let path = package.map(|x| x.path.as_str());
+        return Err(anyhow::anyhow!(
+            "0 commits were found from command:\n\t\t git log {}",
+            log_args(name.as_deref(), path).join(" ")
+        ));
+    }
+    println!("{}", rendered(&examined)?);
+
+    let bad = examined.iter().filter(|x| !x.is_valid()).count();
+    if bad > 0 {
+        return Err(anyhow::anyhow!(
+            "Commit History not cc compliant: found {} bad commits out of {}",
+            bad,
+            examined.len()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_message, clean_message};
+    use crate::config::Config;
+
+    #[test]
+    fn clean() {
+        let message = "\nfeat: new flag  \n\n\n# Please enter the commit message\nbody\n\n# ------------------------ >8 ------------------------\ndiff --git a/x b/x\n";
+        assert_eq!(clean_message(message, '#'), "feat: new flag\n\nbody\n");
+        assert_eq!(clean_message("; comment\nfix: typo\n", ';'), "fix: typo\n");
+        assert_eq!(clean_message("# only a comment\n\n", '#'), "");
+    }
+
+    #[test]
+    fn message() {
+        let config = Config::default();
+        assert!(check_message("feat(cli): new flag\n# comment\n", &config).is_ok());
+        assert!(check_message("# nothing to commit\n", &config).is_err());
+        let err = check_message("bad commit\n", &config).unwrap_err();
+        assert!(!err.to_string().contains('\n'));
+    }
+}
@@ -0,0 +1,348 @@
+use crate::utils::{git, plan};
+use clap::Subcommand;
+use log::{info, warn};
+use std::path::{Path, PathBuf};
+
+/// Line identifying the hooks written by ruvex.
+const MARKER: &str = "# installed by ruvex hooks install, remove with ruvex hooks uninstall";
+
+/// Suffix of a pre-existing hook moved aside, it runs before the ruvex one.
+const CHAINED_SUFFIX: &str = ".ruvex-chained";
+
+/// Hook validating the message of every new commit.
+const COMMIT_MSG: &str = "commit-msg";
+
+/// Hook validating the commits about to be pushed.
+const PRE_PUSH: &str = "pre-push";
+
+/// `commit-msg` script, `{ruvex}` is replaced by the ruvex invocation.
+const COMMIT_MSG_SCRIPT: &str = r#"#!/bin/sh
+{marker}
+chained="$0{chained}"
+if [ -x "$chained" ]; then
+    "$chained" "$@" || exit $?
+fi
+exec {ruvex} check --message-file "$1"
+"#;
+
+/// `pre-push` script, `{ruvex}` is replaced by the ruvex invocation.
+///
+/// Only the commits not yet on the remote are checked: the ones after the
+/// remote tip of the ref, or after every remote-tracking ref of a new branch.
+const PRE_PUSH_SCRIPT: &str = r#"#!/bin/sh
+{marker}
+input=$(cat)
+chained="$0{chained}"
+if [ -x "$chained" ]; then
+    printf '%s\n' "$input" | "$chained" "$@" || exit $?
+fi
+is_zero() {
+    case "$1" in
+        *[!0]*) return 1 ;;
+    esac
+}
+printf '%s\n' "$input" | while read -r local_ref local_sha remote_ref remote_sha; do
+    if [ -z "$local_sha" ] || is_zero "$local_sha"; then
+        continue
+    fi
+    if is_zero "$remote_sha"; then
+        exclude=$(git for-each-ref --format='^%(objectname)' "refs/remotes/$1")
+    else
+        exclude="^$remote_sha"
+    fi
+    if [ -n "$(git rev-list -n 1 "$local_sha" $exclude)" ]; then
+        {ruvex} check "$local_sha" $exclude || exit 1
+    fi
+done
+"#;
+
+/// Action of the `hooks` command.
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum HooksAction {
+    ///install the commit-msg hook, an existing hook is kept and run first
+    Install {
+        ///also install a pre-push hook checking the commits being pushed
+        #[arg(long)]
+        pre_push: bool,
+    },
+    ///remove the ruvex hooks and restore the hooks they chained
+    Uninstall,
+    ///print which hooks are installed
+    Status,
+}
+
+/// State of a hook file.
+#[derive(Debug, PartialEq)]
+enum HookState {
+    /// No hook.
+    Missing,
+    /// A hook not written by ruvex.
+    Foreign,
+    /// A ruvex hook, `chained` when it runs a pre-existing hook first.
+    Installed { chained: bool },
+}
+
+/// Expands a leading `~/` of `path` to the `home` directory, as git does.
+///
+/// # Errors
+/// - Returns [`Err`] if `path` starts with `~/` and `home` is `None`.
+fn expand_home(path: &str, home: Option<&Path>) -> anyhow::Result<PathBuf> {
+    let Some(rest) = path.strip_prefix("~/") else {
+        return Ok(PathBuf::from(path));
+    };
+    match home {
+        Some(home) => Ok(home.join(rest)),
+        None => Err(anyhow::anyhow!("HOME is not set, can't expand {}", path)),
+    }
+}
+
+/// Returns the directory git runs the hooks from, `core.hooksPath` when set.
+///
+/// A relative `core.hooksPath` is relative to the top of the working tree, as
+/// hooks run from there.
+fn hooks_dir() -> anyhow::Result<PathBuf> {
+    if let Some(path) = git::config_value("core.hooksPath")? {
+        let home = std::env::var_os("HOME");
+        let path = expand_home(&path, home.as_deref().map(Path::new))?;
+        let top = String::from_utf8(git::rev_parse(&["--show-toplevel"])?.stdout)?;
+        return Ok(Path::new(top.trim_end()).join(path));
+    }
+    let path = String::from_utf8(git::rev_parse(&["--git-path", "hooks"])?.stdout)?;
+    Ok(PathBuf::from(path.trim_end()))
+}
+
+/// Returns the path a pre-existing `hook` is moved to.
+fn chained_path(hook: &Path) -> PathBuf {
+    let mut path = hook.as_os_str().to_owned();
+    path.push(CHAINED_SUFFIX);
+    PathBuf::from(path)
+}
+
+/// Returns the state of the `hook` file.
+fn hook_state(hook: &Path) -> HookState {
+    match std::fs::read(hook) {
+        Ok(content) if String::from_utf8_lossy(&content).contains(MARKER) => HookState::Installed {
+            chained: chained_path(hook).exists(),
+        },
+        Ok(_) => HookState::Foreign,
+        Err(_) => HookState::Missing,
+    }
+}
+
+/// Quotes `text` for a POSIX shell.
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+/// Renders a hook `script` running ruvex with the `config_path` in use.
+fn render_script(script: &str, config_path: Option<&Path>) -> String {
+    let ruvex = match config_path {
+        Some(path) => format!("ruvex -c {}", shell_quote(&path.display().to_string())),
+        None => "ruvex".to_owned(),
+    };
+    script
+        .replace("{marker}", MARKER)
+        .replace("{chained}", CHAINED_SUFFIX)
+        .replace("{ruvex}", &ruvex)
+}
+
+/// Writes the ruvex `script` to `hook`, a foreign hook already there is moved
+/// to its chained path so it keeps running first.
+///
+/// # Errors
+/// - Returns [`Err`] if a foreign hook and a chained hook both exist.
+fn install_hook(hook: &Path, script: &str, dry_run: bool) -> anyhow::Result<()> {
+    let chained = chained_path(hook);
+    if hook_state(hook) == HookState::Foreign {
+        if chained.exists() {
+            return Err(anyhow::anyhow!(
+                "{} and {} both exist, merge them before installing the ruvex hook",
+                hook.display(),
+                chained.display()
+            ));
+        }
+        if dry_run {
+            plan::report(&format!("move {} to {}", hook.display(), chained.display()));
+        } else {
+            std::fs::rename(hook, &chained)?;
+            info!(
+                "{} moved to {}, it runs first",
+                hook.display(),
+                chained.display()
+            );
+        }
+    }
+    plan::write_file(hook, script, dry_run)?;
+    if !dry_run {
+        make_executable(hook)?;
+    }
+    Ok(())
+}
+
+/// Removes the ruvex `hook` and moves back the hook it chained, a foreign
+/// hook is left untouched.
+fn uninstall_hook(hook: &Path, dry_run: bool) -> anyhow::Result<()> {
+    let HookState::Installed { chained } = hook_state(hook) else {
+        if hook.exists() {
+            warn!("{} was not installed by ruvex, leaving it", hook.display());
+        }
+        return Ok(());
+    };
+    let chained_hook = chained_path(hook);
+    if dry_run {
+        plan::report(&format!("remove {}", hook.display()));
+        if chained {
+            plan::report(&format!(
+                "move {} to {}",
+                chained_hook.display(),
+                hook.display()
+            ));
+        }
+        return Ok(());
+    }
+    std::fs::remove_file(hook)?;
+    info!("{} removed", hook.display());
+    if chained {
+        std::fs::rename(&chained_hook, hook)?;
+        info!("{} restored", hook.display());
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> anyhow::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Installs, removes or reports the ruvex git hooks of the repository.
+///
+/// The `commit-msg` hook runs `ruvex check --message-file`, the optional
+/// `pre-push` hook runs `ruvex check` on the commits being pushed. Hooks
+/// already present are chained: they are moved aside and run first.
+///
+/// # Arguments
+/// - `action`      – What to do with the hooks.
+///
+/// - `config_path` – Config file passed to ruvex by the hooks, the default one when `None`.
+///
+/// - `dry_run`     – Print the changes instead of applying them.
+///
+/// # Errors
+/// - Returns [`Err`] if the current directory is not in a git repository.
+/// - Returns [`Err`] if a hook file can't be written, moved or removed.
+pub fn hooks(action: HooksAction, config_path: Option<&Path>, dry_run: bool) -> anyhow::Result<()> {
+    let dir = hooks_dir()?;
+    match action {
+        HooksAction::Install { pre_push } => {
+            if !dir.exists() {
+                if dry_run {
+                    plan::report(&format!("create directory {}", dir.display()));
+                } else {
+                    std::fs::create_dir_all(&dir)?;
+                }
+            }
+            let config_path =
+                match config_path {
+                    Some(path) => Some(path.canonicalize().map_err(|e| {
+                        anyhow::anyhow!("couldn't resolve {}: {}", path.display(), e)
+                    })?),
+                    None => None,
+                };
+            let mut scripts = vec![(COMMIT_MSG, COMMIT_MSG_SCRIPT)];
+            if pre_push {
+                scripts.push((PRE_PUSH, PRE_PUSH_SCRIPT));
+            }
+            for (name, script) in scripts {
+                let script = render_script(script, config_path.as_deref());
+                install_hook(&dir.join(name), &script, dry_run)?;
+            }
+        }
+        HooksAction::Uninstall => {
+            for name in [COMMIT_MSG, PRE_PUSH] {
+                uninstall_hook(&dir.join(name), dry_run)?;
+            }
+        }
+        HooksAction::Status => {
+            println!("hooks directory: {}", dir.display());
+            for name in [COMMIT_MSG, PRE_PUSH] {
+                let state = match hook_state(&dir.join(name)) {
+                    HookState::Missing => "not installed",
+                    HookState::Foreign => "not installed, a hook not managed by ruvex is present",
+                    HookState::Installed { chained: false } => "installed",
+                    HookState::Installed { chained: true } => {
+                        "installed, running the previous hook first"
+                    }
+                };
+                println!("{}: {}", name, state);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        chained_path, expand_home, hook_state, install_hook, render_script, uninstall_hook,
+        HookState, COMMIT_MSG_SCRIPT,
+    };
+    use std::path::Path;
+
+    #[test]
+    fn render() {
+        let script = render_script(COMMIT_MSG_SCRIPT, Some(Path::new("/it's/ruvex.yaml")));
+        assert!(script.contains("chained=\"$0.ruvex-chained\""));
+        assert!(
+            script.ends_with("exec ruvex -c '/it'\\''s/ruvex.yaml' check --message-file \"$1\"\n")
+        );
+        assert!(!render_script(COMMIT_MSG_SCRIPT, None).contains(" -c "));
+    }
+
+    #[test]
+    fn chain_and_restore() {
+        let dir = std::env::temp_dir().join(format!("ruvex-hooks-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let hook = dir.join("commit-msg");
+        std::fs::write(&hook, "#!/bin/sh\nexit 0\n").unwrap();
+        let script = render_script(COMMIT_MSG_SCRIPT, None);
+
+        install_hook(&hook, &script, false).unwrap();
+        assert_eq!(hook_state(&hook), HookState::Installed { chained: true });
+        // Installing again refreshes the hook and keeps the chained one
+        install_hook(&hook, &script, false).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(chained_path(&hook)).unwrap(),
+            "#!/bin/sh\nexit 0\n"
+        );
+
+        uninstall_hook(&hook, false).unwrap();
+        assert_eq!(hook_state(&hook), HookState::Foreign);
+        assert!(!chained_path(&hook).exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn home() {
+        let home = Some(Path::new("/home/dev"));
+        assert_eq!(
+            expand_home("~/hooks", home).unwrap(),
+            Path::new("/home/dev/hooks")
+        );
+        assert_eq!(
+            expand_home("githooks", home).unwrap(),
+            Path::new("githooks")
+        );
+        assert_eq!(
+            expand_home("githooks", None).unwrap(),
+            Path::new("githooks")
+        );
+        assert!(expand_home("~/hooks", None).is_err());
+    }
+}
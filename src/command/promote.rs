@@ -0,0 +1,149 @@
+use crate::command::check::parse_history;
+use crate::command::tag::{
+    latest_tag, list_tags, parse_tags, tag_message, VersionQuery, CALVER_UNSUPPORTED,
+};
+use crate::config::{Config, Package, Scheme};
+use crate::utils::pep440::Pep440;
+use crate::utils::semver::SemVer;
+use crate::utils::tag_pattern::TagPattern;
+use crate::utils::version::VersionScheme;
+use crate::utils::{git, plan};
+use log::info;
+
+/// Promotes the latest pre-release tag, of the repository or of `package`,
+/// to its stable version.
+///
+/// The stable tag (e.g. `1.3.0` for `1.3.0-rc.4`) is created on the same
+/// commit as the pre-release, without re-deriving the bump from the commits.
+///
+/// # Arguments
+/// - `package` – Name of the `packages` entry to promote, required when packages are configured.
+///
+/// - `dry_run` – Print the tag instead of creating it.
+///
+/// - `config`  – Project-level configuration used during commit parsing.
+///
+/// # Errors
+/// - Returns [`Err`] if `packages` are configured but `package` is not set.
+/// - Returns [`Err`] if the latest tag is not a pre-release.
+/// - Returns [`Err`] if the stable version of the latest pre-release, or a
+///   greater one, is already tagged.
+pub fn promote(package: Option<String>, dry_run: bool, config: &Config) -> anyhow::Result<()> {
+    let package = match package {
+        Some(ref name) => Some(config.package(name)?),
+        None if config.packages.is_some() => {
+            return Err(anyhow::anyhow!(
+                "packages are configured, select the one to promote"
+            ))
+        }
+        None => None,
+    };
+    match config.scheme(package.map(|x| x.name.as_str()))? {
+        Scheme::SemVer => promote_latest::<SemVer>(package, dry_run, config),
+        Scheme::Pep440 => promote_latest::<Pep440>(package, dry_run, config),
+        Scheme::CalVer => Err(anyhow::anyhow!(CALVER_UNSUPPORTED)),
+    }
+}
+
+/// Returns the latest tag of `tags`, which must be a pre-release, and its
+/// stable version.
+///
+/// # Errors
+/// - Returns [`Err`] if there is no tag or the latest one is not a pre-release.
+/// - Returns [`Err`] if a stable release greater or equal to the promoted one exists.
+fn promotion<'a, V: VersionScheme>(
+    tags: &'a [V],
+    pattern: &TagPattern,
+    config: &Config,
+) -> anyhow::Result<(&'a V, V)> {
+    let Some(latest) = latest_tag(tags, false, config) else {
+        return Err(anyhow::anyhow!("no tag found, nothing to promote"));
+    };
+    if !latest.is_prerelease() {
+        return Err(anyhow::anyhow!(
+            "the latest tag {} is not a pre-release, nothing to promote",
+            pattern.format(latest)
+        ));
+    }
+    let stable = latest.release();
+    if let Some(released) = tags
+        .iter()
+        .filter(|x| !x.is_prerelease() && **x >= stable)
+        .max()
+    {
+        return Err(anyhow::anyhow!(
+            "{} is already released, refusing to promote {}",
+            pattern.format(released),
+            pattern.format(latest)
+        ));
+    }
+    Ok((latest, stable))
+}
+
+/// Promotes the latest pre-release tag of the versioning scheme `V`.
+fn promote_latest<V: VersionScheme>(
+    package: Option<&Package>,
+    dry_run: bool,
+    config: &Config,
+) -> anyhow::Result<()> {
+    let pattern = match package {
+        Some(package) => TagPattern::from_package(package),
+        None => TagPattern::from_config(config),
+    };
+    let tags: Vec<V> = parse_tags(&list_tags(&VersionQuery::default(), config)?, &pattern);
+    let (pre_release, stable) = promotion(&tags, &pattern, config)?;
+
+    // Summarise every commit since the previous stable release.
+    let source = pattern.format(pre_release);
+    let previous: Option<&V> = tags
+        .iter()
+        .filter(|x| !x.is_prerelease() && **x < stable)
+        .max();
+    let range = match previous {
+        Some(prev) => format!("{}..{}", pattern.format(prev), source),
+        None => source.clone(),
+    };
+    let (commits, _) = parse_history(Some(std::slice::from_ref(&range)), package, config)?;
+    let message = tag_message(&stable, &commits, config);
+
+    let tag_name = pattern.format(&stable);
+    // Peel the pre-release tag so the new tag points to the commit itself.
+    let target = format!("{}^{{commit}}", source);
+    if dry_run {
+        plan::report(&format!(
+            "create tag {} on {} with message:\n{}",
+            tag_name, source, message
+        ));
+        return Ok(());
+    }
+    git::annotated_tag(&tag_name, &message, &target)?;
+    info!("promoted {} to {}", source, tag_name);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::promotion;
+    use crate::config::Config;
+    use crate::utils::semver::SemVer;
+    use crate::utils::tag_pattern::TagPattern;
+
+    fn promoted(tags: &[&str]) -> anyhow::Result<String> {
+        let config = Config::default();
+        let tags: Vec<SemVer> = tags.iter().map(|x| x.parse().unwrap()).collect();
+        let pattern = TagPattern::from_config(&config);
+        Ok(promotion(&tags, &pattern, &config)?.1.to_string())
+    }
+
+    #[test]
+    fn latest_pre_release() {
+        assert_eq!(
+            promoted(&["1.2.0", "1.3.0-rc.1", "1.3.0-rc.2"]).unwrap(),
+            "1.3.0"
+        );
+        // A greater release was made since the pre-release
+        assert!(promoted(&["1.3.0-rc.1", "1.4.0"]).is_err());
+        assert!(promoted(&["1.2.0"]).is_err());
+        assert!(promoted(&[]).is_err());
+    }
+}
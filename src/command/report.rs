@@ -0,0 +1,294 @@
+use crate::command::check::CheckedCommit;
+use crate::config::Severity;
+use crate::utils::errors::ConventionalCommitParseError;
+use crate::utils::lint::Violation;
+use crate::utils::{ConventionalCommit, SemVerChangeType};
+use clap::ValueEnum;
+use serde_json::{json, Value};
+
+/// SARIF schema the `sarif` output conforms to.
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Rule reported by the `sarif` output for every commit.
+const SARIF_RULE: &str = "conventional-commit";
+
+/// Format of the `check` report.
+#[derive(Debug, Clone, Copy, PartialEq, Default, ValueEnum)]
+pub enum CheckOutput {
+    /// Decorated `git log` graph and a table of the non-compliant commits.
+    #[default]
+    Table,
+    /// A JSON document listing every commit and a summary.
+    Json,
+    /// A JUnit XML test suite, one test case per commit.
+    Junit,
+    /// A SARIF 2.1.0 log, one result per commit.
+    Sarif,
+}
+
+/// Returns the parsed fields of `commit` as a JSON object.
+fn commit_fields(commit: &ConventionalCommit) -> Value {
+    json!({
+        "type": commit.commit_type(),
+        "scope": commit.scope(),
+        "breaking": *commit.change() == SemVerChangeType::Major,
+        "change": commit.change().to_string(),
+        "description": commit.short_description(),
+        "body": commit.body(),
+        "footer": commit.footer(),
+    })
+}
+
+/// Returns the JSON object of a rejection `error`.
+fn error_fields(error: &ConventionalCommitParseError) -> Value {
+    json!({
+        "code": error.code(),
+        "message": error.to_string(),
+    })
+}
+
+/// Returns the JSON object of a lint rule `violation`.
+fn violation_fields(violation: &Violation) -> Value {
+    json!({
+        "rule": violation.rule,
+        "severity": violation.severity.to_string(),
+        "message": violation.message,
+    })
+}
+
+/// Returns the number of valid and rejected commits of `examined`.
+fn summary(examined: &[CheckedCommit]) -> (usize, usize) {
+    let valid = examined.iter().filter(|x| x.is_valid()).count();
+    (valid, examined.len() - valid)
+}
+
+/// Renders `examined` as a JSON document: every commit with its parsed fields
+/// or its error and its lint rule violations, and a summary.
+pub(crate) fn json(examined: &[CheckedCommit]) -> anyhow::Result<String> {
+    let commits: Vec<Value> = examined
+        .iter()
+        .map(|x| {
+            let mut commit = json!({
+                "sha": x.sha,
+                "message": x.message,
+                "valid": x.is_valid(),
+                "violations": x.violations.iter().map(violation_fields).collect::<Vec<Value>>(),
+            });
+            match x.result {
+                Ok(ref parsed) => commit["commit"] = commit_fields(parsed),
+                Err(ref error) => commit["error"] = error_fields(error),
+            }
+            commit
+        })
+        .collect();
+    let (valid, invalid) = summary(examined);
+    let report = json!({
+        "commits": commits,
+        "summary": {
+            "total": examined.len(),
+            "valid": valid,
+            "invalid": invalid,
+        },
+    });
+    Ok(serde_json::to_string_pretty(&report)?)
+}
+
+/// Escapes `text` for an XML attribute or text node.
+fn xml_escape(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '&' => "&amp;".to_owned(),
+            '<' => "&lt;".to_owned(),
+            '>' => "&gt;".to_owned(),
+            '"' => "&quot;".to_owned(),
+            '\'' => "&apos;".to_owned(),
+            _ => c.to_string(),
+        })
+        .collect()
+}
+
+/// Renders `examined` as a JUnit XML test suite where every commit is a test
+/// case, failed when the commit is rejected. The warnings of the valid
+/// commits are kept in their `system-out`.
+pub(crate) fn junit(examined: &[CheckedCommit]) -> anyhow::Result<String> {
+    let (_, invalid) = summary(examined);
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"ruvex check\" tests=\"{0}\" failures=\"{1}\">\n  <testsuite name=\"conventional commits\" tests=\"{0}\" failures=\"{1}\">\n",
+        examined.len(),
+        invalid
+    ));
+    for commit in examined {
+        let header = commit.message.lines().next().unwrap_or_default();
+        let name = xml_escape(&format!("{} {}", commit.sha, header));
+        let problems = xml_escape(&commit.problems().join("\n"));
+        if !commit.is_valid() {
+            let kind = match commit.result {
+                Ok(_) => "lint",
+                Err(ref error) => error.code(),
+            };
+            xml.push_str(&format!(
+                "    <testcase classname=\"commits\" name=\"{}\">\n      <failure type=\"{}\" message=\"{}\">{}</failure>\n    </testcase>\n",
+                name,
+                kind,
+                problems,
+                xml_escape(&commit.message)
+            ));
+        } else if !problems.is_empty() {
+            xml.push_str(&format!(
+                "    <testcase classname=\"commits\" name=\"{}\">\n      <system-out>{}</system-out>\n    </testcase>\n",
+                name, problems
+            ));
+        } else {
+            xml.push_str(&format!(
+                "    <testcase classname=\"commits\" name=\"{}\"/>\n",
+                name
+            ));
+        }
+    }
+    xml.push_str("  </testsuite>\n</testsuites>");
+    Ok(xml)
+}
+
+/// Returns the SARIF result of a lint rule `violation` of `sha`.
+fn violation_result(sha: &str, violation: &Violation) -> Value {
+    let level = match violation.severity {
+        Severity::Error => "error",
+        _ => "warning",
+    };
+    json!({
+        "ruleId": violation.rule,
+        "kind": "fail",
+        "level": level,
+        "message": { "text": format!("{}: {}", sha, violation.message) },
+        "logicalLocations": [{ "name": sha, "kind": "commit" }],
+        "partialFingerprints": { "commitSha": sha },
+    })
+}
+
+/// Renders `examined` as a SARIF 2.1.0 log holding one result per commit,
+/// `pass` for the compliant ones, and one per lint rule violation. The
+/// summary is kept in the run properties.
+pub(crate) fn sarif(examined: &[CheckedCommit]) -> anyhow::Result<String> {
+    let results: Vec<Value> = examined
+        .iter()
+        .flat_map(|x| {
+            let (kind, level, text) = match x.result {
+                Ok(ref parsed) => (
+                    "pass",
+                    "none",
+                    format!("{} is a Conventional Commit: {}", x.sha, parsed.header()),
+                ),
+                Err(ref error) => (
+                    "fail",
+                    "error",
+                    format!("{} is not a Conventional Commit: {}", x.sha, error),
+                ),
+            };
+            let mut result = json!({
+                "ruleId": SARIF_RULE,
+                "kind": kind,
+                "level": level,
+                "message": { "text": text },
+                "logicalLocations": [{ "name": x.sha, "kind": "commit" }],
+                "partialFingerprints": { "commitSha": x.sha },
+            });
+            if let Err(ref error) = x.result {
+                result["properties"] = json!({ "code": error.code() });
+            }
+            std::iter::once(result).chain(x.violations.iter().map(|v| violation_result(&x.sha, v)))
+        })
+        .collect();
+    let mut rule_ids: Vec<&str> = examined
+        .iter()
+        .flat_map(|x| x.violations.iter().map(|v| v.rule))
+        .collect();
+    rule_ids.sort_unstable();
+    rule_ids.dedup();
+    let mut rules = vec![json!({
+        "id": SARIF_RULE,
+        "shortDescription": {
+            "text": "Commit messages follow the Conventional Commits specification"
+        },
+    })];
+    rules.extend(rule_ids.into_iter().map(|x| json!({ "id": x })));
+    let (valid, invalid) = summary(examined);
+    let log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "ruvex",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+            "properties": {
+                "total": examined.len(),
+                "valid": valid,
+                "invalid": invalid,
+            },
+        }],
+    });
+    Ok(serde_json::to_string_pretty(&log)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{json, junit, sarif};
+    use crate::command::check::CheckedCommit;
+    use crate::config::{Config, Rule, Rules, Severity};
+
+    fn examined() -> Vec<CheckedCommit> {
+        let config = Config {
+            rules: Some(Rules {
+                subject_full_stop: Some(Rule {
+                    severity: Severity::Warn,
+                    value: None,
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        ["feat(cli): new <flag>.", "bad commit"]
+            .iter()
+            .zip(["a1b2c3d", "e4f5a6b"])
+            .map(|(message, sha)| CheckedCommit::new(sha.to_string(), message.to_string(), &config))
+            .collect()
+    }
+
+    #[test]
+    fn json_report() {
+        let report: serde_json::Value = serde_json::from_str(&json(&examined()).unwrap()).unwrap();
+        assert_eq!(report["commits"][0]["commit"]["scope"], "cli");
+        assert_eq!(report["commits"][0]["commit"]["change"], "minor");
+        assert_eq!(report["commits"][0]["violations"][0]["severity"], "warn");
+        assert_eq!(report["commits"][1]["error"]["code"], "missing-colon");
+        assert_eq!(report["summary"]["total"], 2);
+        assert_eq!(report["summary"]["invalid"], 1);
+    }
+
+    #[test]
+    fn junit_report() {
+        let report = junit(&examined()).unwrap();
+        assert!(report.contains("tests=\"2\" failures=\"1\""));
+        assert!(report.contains("name=\"a1b2c3d feat(cli): new &lt;flag&gt;.\">"));
+        assert!(report.contains("<system-out>warn subject-full-stop: "));
+        assert!(report.contains("<failure type=\"missing-colon\""));
+    }
+
+    #[test]
+    fn sarif_report() {
+        let report: serde_json::Value = serde_json::from_str(&sarif(&examined()).unwrap()).unwrap();
+        let run = &report["runs"][0];
+        assert_eq!(report["version"], "2.1.0");
+        assert_eq!(run["results"][0]["kind"], "pass");
+        assert_eq!(run["results"][1]["ruleId"], "subject-full-stop");
+        assert_eq!(run["results"][1]["level"], "warning");
+        assert_eq!(run["results"][2]["level"], "error");
+        assert_eq!(run["results"][2]["properties"]["code"], "missing-colon");
+        assert_eq!(run["properties"]["invalid"], 1);
+    }
+}
@@ -1,203 +1,826 @@
 use log::debug;
 use log::info;
 
-use crate::command::check::check;
-use crate::config::Config;
+use crate::command::check::{error_table, parse_history, RejectedCommit, DEFAULT_WRAP_WIDTH};
+use crate::config::{Cascade, Config, Scheme};
+use crate::utils::build_meta::{self, BuildInfo};
+use crate::utils::calver::CalVerFormat;
+use crate::utils::cc::footer_value;
+use crate::utils::date::Date;
 use crate::utils::git;
+use crate::utils::manifest::DependencyGraph;
+use crate::utils::pep440::Pep440;
+use crate::utils::plan;
 use crate::utils::semver::SemVer;
+use crate::utils::tag_pattern::TagPattern;
+use crate::utils::version::VersionScheme;
 use crate::utils::CCVec;
+use crate::utils::ConventionalCommit;
 use crate::utils::SemVerChangeType;
+use prettytable::{Attr, Cell, Row, Table};
+use std::fmt::Display;
 use std::process::Output;
 // use ruvex_config::Config;
 
-fn parse_tags(to_parse: &str) -> Vec<SemVer> {
-    let default = SemVer {
-        major: 0,
-        minor: 0,
-        patch: 0,
-        pre_release: None,
-        build_meta: None,
-    };
-    let tags: Vec<SemVer> = to_parse
+/// Message used for annotated tags when `tag.message` is not configured.
+const DEFAULT_TAG_MESSAGE: &str = "Release {version}\n\n{summary}";
+
+/// Footer token forcing the next version, e.g. `Release-As: 2.0.0`.
+const RELEASE_AS_TOKEN: &str = "Release-As";
+
+/// Renders the annotated tag message for `version` by substituting the
+/// `{version}` and `{summary}` placeholders of `template`.
+fn render_tag_message(
+    template: &str,
+    version: &impl Display,
+    commits: &[ConventionalCommit],
+) -> String {
+    let summary = commits
+        .iter()
+        .map(|x| format!("- {} ({})", x.header(), x.short_sha))
+        .collect::<Vec<String>>()
+        .join("\n");
+    template
+        .replace("{version}", &version.to_string())
+        .replace("{summary}", &summary)
+}
+
+/// Renders the annotated tag message for `version` from the `tag.message`
+/// template, falling back to [`DEFAULT_TAG_MESSAGE`].
+pub(crate) fn tag_message(
+    version: &impl Display,
+    commits: &[ConventionalCommit],
+    config: &Config,
+) -> String {
+    let template = config
+        .tag
+        .as_ref()
+        .and_then(|x| x.message.as_deref())
+        .unwrap_or(DEFAULT_TAG_MESSAGE);
+    render_tag_message(template, version, commits)
+}
+
+/// Fails if HEAD already carries a tag matching `pattern` holding a version
+/// accepted by `is_version`, as tagging it again would release the same
+/// commit twice.
+fn ensure_head_untagged(
+    pattern: &TagPattern,
+    is_version: impl Fn(&str) -> bool,
+) -> anyhow::Result<()> {
+    let head_tags = String::from_utf8(git::tag(&["--points-at", "HEAD"])?.stdout)?;
+    let existing = head_tags
+        .lines()
+        .find(|x| pattern.strip(x).is_some_and(&is_version));
+    if let Some(existing) = existing {
+        return Err(anyhow::anyhow!(
+            "HEAD is already tagged as {}, refusing to create a new tag",
+            existing
+        ));
+    }
+    Ok(())
+}
+
+/// Fails when there is nothing to release.
+fn ensure_release_worthy(change: &SemVerChangeType, version: &impl Display) -> anyhow::Result<()> {
+    if *change == SemVerChangeType::None {
+        return Err(anyhow::anyhow!(
+            "no release worthy commits found, refusing to tag {}",
+            version
+        ));
+    }
+    Ok(())
+}
+
+/// Writes the annotated tag for `version` on HEAD.
+///
+/// When `dry_run` is `true` the tag is only reported.
+fn create_tag(
+    version: &impl Display,
+    commits: &[ConventionalCommit],
+    pattern: &TagPattern,
+    dry_run: bool,
+    config: &Config,
+) -> anyhow::Result<()> {
+    let message = tag_message(version, commits, config);
+    let tag_name = pattern.format(version);
+
+    if dry_run {
+        plan::report(&format!(
+            "create tag {} on HEAD with message:\n{}",
+            tag_name, message
+        ));
+        return Ok(());
+    }
+    git::annotated_tag(&tag_name, &message, "HEAD")?;
+    info!("created tag {}", tag_name);
+    Ok(())
+}
+
+/// Parses the output of `git tag`, keeping only the tags matching `pattern`.
+pub(crate) fn parse_tags<V: VersionScheme>(to_parse: &str, pattern: &TagPattern) -> Vec<V> {
+    let default = V::initial();
+    let tags: Vec<V> = to_parse
         .split('\n')
-        .map(|x| x.parse::<SemVer>().unwrap_or(default.clone()))
+        .filter_map(|x| V::parse(pattern.strip(x)?))
         .filter(|x| *x != default) // filter the 0.0.0
         .collect();
     tags
 }
 
-#[allow(dead_code)]
-fn increase_semver(
-    to_increase: SemVer,
+/// Returns `true` if `text` is a version of the scheme `V`.
+fn is_version<V: VersionScheme>(text: &str) -> bool {
+    V::parse(text).is_some()
+}
+
+/// Returns the change needed to go from the stable `base` to `target`.
+fn change_between<V: VersionScheme>(base: &V, target: &V) -> SemVerChangeType {
+    [
+        SemVerChangeType::Major,
+        SemVerChangeType::Minor,
+        SemVerChangeType::Patch,
+    ]
+    .into_iter()
+    .find(|x| base.bump(x.clone()) <= *target)
+    .unwrap_or(SemVerChangeType::None)
+}
+
+/// Computes the next `channel` pre-release after `latest`.
+///
+/// While the `change` found since `latest` does not exceed the bump the
+/// current pre-release already represents (e.g. a minor for `1.3.0-rc.1`
+/// after `1.2.0`), the counter is increased. Otherwise a new core version
+/// is computed from the latest stable tag and the counter starts again.
+fn next_prerelease<V: VersionScheme>(
+    latest: &V,
+    tags: &[V],
     change: SemVerChangeType,
-    prerelease: Option<String>,
-    build_meta: Option<String>,
-) -> SemVer {
-    match change {
-        SemVerChangeType::Major => increase_major(to_increase, prerelease, build_meta),
-        SemVerChangeType::Minor => increase_minor(to_increase, prerelease, build_meta),
-        SemVerChangeType::Patch => increase_patch(to_increase, prerelease, build_meta),
-        SemVerChangeType::None => to_increase,
-    }
-}
-
-#[allow(dead_code)]
-fn increase_minor(
-    to_increase: SemVer,
-    prerelease: Option<String>,
-    build_meta: Option<String>,
-) -> SemVer {
-    SemVer {
-        major: to_increase.major + 1,
-        minor: 0,
-        patch: 0,
-        pre_release: prerelease,
-        build_meta,
-    }
-}
-#[allow(dead_code)]
-fn increase_major(
-    to_increase: SemVer,
-    prerelease: Option<String>,
-    build_meta: Option<String>,
-) -> SemVer {
-    SemVer {
-        major: to_increase.major + 1,
-        minor: 0,
-        patch: 0,
-        pre_release: prerelease,
-        build_meta,
-    }
-}
-#[allow(dead_code)]
-fn increase_patch(
-    to_increase: SemVer,
-    prerelease: Option<String>,
-    build_meta: Option<String>,
-) -> SemVer {
-    SemVer {
-        major: to_increase.major + 1,
-        minor: 0,
-        patch: 0,
-        pre_release: prerelease,
-        build_meta,
-    }
-}
-
-fn latest_tag<'a>(
-    tags: &'a [SemVer],
+    channel: &str,
+) -> anyhow::Result<V> {
+    if change == SemVerChangeType::None {
+        return Ok(latest.clone());
+    }
+
+    let core = match latest.is_prerelease() {
+        true => {
+            let pending_core = latest.release();
+            let stable = tags
+                .iter()
+                .filter(|x| !x.is_prerelease() && **x < pending_core)
+                .max()
+                .cloned()
+                .unwrap_or(V::initial());
+            match change > change_between(&stable, &pending_core) {
+                true => stable.bump(change),
+                false => pending_core,
+            }
+        }
+        false => latest.bump(change),
+    };
+
+    let counter = tags
+        .iter()
+        .filter(|x| x.release() == core)
+        .filter_map(|x| x.pre_release_counter(channel))
+        .max()
+        .map_or(1, |x| x + 1);
+    core.with_pre_release(channel, counter)
+}
+
+pub(crate) fn latest_tag<'a, V: VersionScheme>(
+    tags: &'a [V],
     ignore_prereleases: bool,
     config: &Config,
-) -> Option<&'a SemVer> {
+) -> Option<&'a V> {
     match ignore_prereleases {
-        true => tags.iter().filter(|x| x.pre_release.is_none()).max(),
+        true => tags.iter().filter(|x| !x.is_prerelease()).max(),
         false => {
             match config.tag.is_some()
                 && config.tag.as_ref().unwrap().ignore_prereleases.is_some()
                 && config.tag.as_ref().unwrap().ignore_prereleases.unwrap()
             {
-                true => tags.iter().filter(|x| x.pre_release.is_none()).max(),
+                true => tags.iter().filter(|x| !x.is_prerelease()).max(),
                 false => tags.iter().max(),
             }
         }
     }
 }
 
-pub fn tag(
-    name: Option<Vec<String>>,
-    merged: Option<String>,
-    no_merged: Option<String>,
-    ignore_prereleases: bool,
-    config: &Config,
-) -> anyhow::Result<()> {
+/// Selects the history and the tags the next version is computed from.
+#[derive(Debug, Default, Clone)]
+pub struct VersionQuery {
+    /// Optional `git log` refs to check instead of `<latest tag>...`.
+    /// The `TAG` keyword is replaced by the latest tag.
+    pub name: Option<Vec<String>>,
+
+    /// Only consider tags merged into this ref.
+    pub merged: Option<String>,
+
+    /// Only consider tags not merged into this ref.
+    pub no_merged: Option<String>,
+
+    /// Skip pre-release tags when looking for the latest tag.
+    pub ignore_prereleases: bool,
+
+    /// Name of the `packages` entry to version. The whole repository is
+    /// versioned when `None`.
+    pub package: Option<String>,
+
+    /// Version to release instead of the computed one, e.g. `1.0.0`.
+    pub release_as: Option<String>,
+}
+
+/// Returns the output of `git tag` listing the tags selected by `query`.
+pub(crate) fn list_tags(query: &VersionQuery, config: &Config) -> anyhow::Result<String> {
+    // `tag.filter` restricts the listed tags to a `git tag -l` pattern
+    let filter = config.tag.as_ref().and_then(|x| x.filter.as_deref());
     let out: Output = {
-        if let Some(merged) = merged {
-            git::tag(&["--merged", &merged])?
-        } else if let Some(no_merged) = no_merged {
-            git::tag(&["--no-merged", &no_merged])?
+        let mut args: Vec<&str> = if let Some(ref merged) = query.merged {
+            vec!["--merged", merged]
+        } else if let Some(ref no_merged) = query.no_merged {
+            vec!["--no-merged", no_merged]
         } else {
-            git::tag(&["-l"])?
-        }
+            vec!["-l"]
+        };
+        args.extend(filter);
+        git::tag(&args)?
     };
 
     let binding = String::from_utf8(out.stdout)?;
     debug!("git tag command result is {:#?}", binding);
+    Ok(binding)
+}
 
-    let tags = parse_tags(&binding);
-    info!("tags found are:");
-    for tag in &tags {
-        print!(" {}", tag);
+/// Returns the `git log` refs of the commits released after the `latest` tag
+/// name, `None` for the whole history.
+fn history_range(latest: Option<String>, query: &VersionQuery) -> Option<Vec<String>> {
+    match (latest, query.name.clone()) {
+        (Some(latest), None) => Some(vec![format!("{}...", latest)]),
+        (None, None) => None,
+        (Some(latest), Some(name)) => Some(
+            name.into_iter()
+                // if TAG keyword is found in name substitute it with latest_tag
+                .map(|x| x.replace("TAG", &latest))
+                .collect(),
+        ),
+        (None, Some(name)) => Some(name),
     }
+}
 
-    let latest_tag = latest_tag(&tags, ignore_prereleases, config);
+/// Error of the commands not supporting the `calver` scheme.
+pub(crate) const CALVER_UNSUPPORTED: &str =
+    "only the tag, version and changelog commands support the calver scheme";
 
-    let (good_commits, _bad_commits) = match (tags.is_empty(), name.is_some()) {
-        (false, false) => check(
-            Some(vec![format!("{}...", latest_tag.unwrap())]),
-            None,
-            config,
-            false,
-        )?,
-        (true, false) => check(None, None, config, false)?,
-        (false, true) => check(
-            Some(
-                name.unwrap()
-                    .into_iter()
-                    // if TAG keyword is found in name substitute it with latest_tag
-                    .map(|x| x.replace("TAG", latest_tag.unwrap().to_string().as_str()))
-                    .collect(),
-            ),
+/// Outcome of the next version computation, shared by the commands that
+/// release a new version.
+pub(crate) struct NextVersion<V: VersionScheme = SemVer> {
+    /// Latest tagged version, `None` when the repository has none.
+    pub(crate) latest: Option<V>,
+    /// Version the release should be tagged with.
+    pub(crate) next: V,
+    /// Highest change found in the commits since `latest`, or the one
+    /// reaching an overridden version.
+    pub(crate) change: SemVerChangeType,
+    /// Bump applied to `latest`, `change` lowered by the `pre_1_0` policy.
+    pub(crate) bump: SemVerChangeType,
+    /// CC compliant commits since `latest`.
+    pub(crate) commits: Vec<ConventionalCommit>,
+    /// Commits since `latest` that are not CC compliant.
+    pub(crate) rejected: Vec<RejectedCommit>,
+    /// Number of commits since `latest`, compliant or not.
+    pub(crate) count: usize,
+    /// `git log` refs the commits were read from, empty for the whole history.
+    pub(crate) range: Vec<String>,
+    /// Where an overridden `next` comes from, e.g. `--release-as`.
+    pub(crate) overridden_by: Option<String>,
+    /// Tag naming pattern the versions were read with.
+    pub(crate) pattern: TagPattern,
+    /// Every tagged version found.
+    pub(crate) tags: Vec<V>,
+}
+
+/// Finds the latest tag and computes the next version from the Conventional
+/// Commits found since then, in the versioning scheme `V`. Nothing is
+/// printed, progress is logged at debug level.
+///
+/// # Arguments
+/// - `query`  – Selects the tags and the history to compute the version from.
+///
+/// - `config` – Project-level configuration used during commit parsing.
+pub(crate) fn next_version<V: VersionScheme>(
+    query: &VersionQuery,
+    config: &Config,
+) -> anyhow::Result<NextVersion<V>> {
+    let package = match query.package {
+        Some(ref name) => Some(config.package(name)?),
+        None => None,
+    };
+    let pattern = match package {
+        Some(package) => TagPattern::from_package(package),
+        None => TagPattern::from_config(config),
+    };
+
+    let binding = list_tags(query, config)?;
+    let tags: Vec<V> = parse_tags(&binding, &pattern);
+    debug!("tags found are: {:?}", tags);
+
+    let latest_tag = latest_tag(&tags, query.ignore_prereleases, config);
+    let range = history_range(latest_tag.map(|x| pattern.format(x)), query);
+    let (commits, rejected) = parse_history(range.as_deref(), package, config)?;
+
+    let semver_change = commits.max_change();
+    debug!("change type is: {:?}", semver_change);
+    let before: V = latest_tag.cloned().unwrap_or(V::initial());
+    let count = commits.len() + rejected.len();
+    let (next, change, bump, overridden_by) = match release_override(query, &commits) {
+        Some((text, source)) => {
+            let next = release_as(text, &source, latest_tag)?;
+            let change = change_between(&before.release(), &next).max(SemVerChangeType::Patch);
+            (next, change.clone(), change, Some(source))
+        }
+        None => (
+            version_for(&before, &tags, semver_change.clone(), count, config)?,
+            semver_change.clone(),
+            bump_change(&before, semver_change, config),
             None,
-            config,
-            false,
-        )?,
-        (true, true) => check(name, None, config, false)?,
+        ),
     };
 
-    let semver_change = good_commits.max_change();
+    Ok(NextVersion {
+        latest: latest_tag.cloned(),
+        next,
+        change,
+        bump,
+        commits,
+        rejected,
+        count,
+        range: range.unwrap_or_default(),
+        overridden_by,
+        pattern,
+        tags,
+    })
+}
 
-    let before_semver: SemVer;
+/// Returns the version forced by `--release-as`, else by the newest
+/// `Release-As` footer of `commits`, along with where it comes from.
+fn release_override<'a>(
+    query: &'a VersionQuery,
+    commits: &'a [ConventionalCommit],
+) -> Option<(&'a str, String)> {
+    if let Some(ref text) = query.release_as {
+        return Some((text, "--release-as".to_owned()));
+    }
+    commits.iter().find_map(|x| {
+        let text = footer_value(x.footer.as_deref()?, RELEASE_AS_TOKEN)?;
+        Some((
+            text,
+            format!("the {} footer of {}", RELEASE_AS_TOKEN, x.short_sha),
+        ))
+    })
+}
 
-    println!("Change type is: {:?}", semver_change);
-    if let Some(tag) = latest_tag {
-        println!("Latest identified SemVer tag is: {}", tag);
-        before_semver = latest_tag.unwrap().clone();
-    } else {
-        println!("Latest identified SemVer tag is: None");
-        before_semver = SemVer {
-            major: 0,
-            minor: 0,
-            patch: 0,
-            pre_release: None,
-            build_meta: None,
-        };
+/// Parses the version forced by `source`, which must be strictly greater
+/// than `latest`.
+fn release_as<V: VersionScheme>(text: &str, source: &str, latest: Option<&V>) -> anyhow::Result<V> {
+    let Some(version) = V::parse(text) else {
+        return Err(anyhow::anyhow!(
+            "{:?} from {} is not a version of the configured scheme",
+            text,
+            source
+        ));
+    };
+    match latest {
+        Some(latest) if version <= *latest => Err(anyhow::anyhow!(
+            "{} from {} must be greater than the latest tag {}",
+            version,
+            source,
+            latest
+        )),
+        _ => Ok(version),
     }
+}
 
-    let current_semver: SemVer = match semver_change {
-        SemVerChangeType::Major => SemVer {
-            major: before_semver.major + 1,
-            minor: 0,
-            patch: 0,
-            pre_release: None,
-            build_meta: None,
-        },
-        SemVerChangeType::Minor => SemVer {
-            major: before_semver.major,
-            minor: before_semver.minor + 1,
-            patch: 0,
-            pre_release: None,
-            build_meta: None,
-        },
-        SemVerChangeType::Patch => SemVer {
-            major: before_semver.major,
-            minor: before_semver.minor,
-            patch: before_semver.patch + 1,
-            pre_release: None,
-            build_meta: None,
-        },
-        SemVerChangeType::None => before_semver,
+/// Returns the bump applied after `latest` for a `change`, lowered by the
+/// `pre_1_0` policy during the initial development.
+pub(crate) fn bump_change<V: VersionScheme>(
+    latest: &V,
+    change: SemVerChangeType,
+    config: &Config,
+) -> SemVerChangeType {
+    match config.pre_1_0 {
+        Some(ref policy) if latest.is_initial_development() => policy.change(change),
+        _ => change,
+    }
+}
+
+/// Computes the version released after `latest` for a `change`, in the
+/// configured pre-release channel and with the configured build metadata.
+fn version_for<V: VersionScheme>(
+    latest: &V,
+    tags: &[V],
+    change: SemVerChangeType,
+    count: usize,
+    config: &Config,
+) -> anyhow::Result<V> {
+    let change = bump_change(latest, change, config);
+    let channel = config.tag.as_ref().and_then(|x| x.pre_release.as_deref());
+    let version = match channel {
+        Some(channel) => next_prerelease(latest, tags, change, channel)?,
+        None => latest.bump(change),
     };
-    println!("Next tag is {}", current_semver);
+
+    match config.tag.as_ref().and_then(|x| x.build_meta.as_deref()) {
+        Some(template) => {
+            Ok(version.with_local(&build_meta::render(template, &BuildInfo::collect(count)?)?))
+        }
+        None => Ok(version),
+    }
+}
+
+impl<V: VersionScheme> NextVersion<V> {
+    /// Raises the release to at least `change`, e.g. when a dependency of the
+    /// package is released.
+    pub(crate) fn raise(
+        &mut self,
+        change: SemVerChangeType,
+        config: &Config,
+    ) -> anyhow::Result<()> {
+        if change <= self.change {
+            return Ok(());
+        }
+        let latest = self.latest.clone().unwrap_or(V::initial());
+        self.next = version_for(&latest, &self.tags, change.clone(), self.count, config)?;
+        self.bump = bump_change(&latest, change.clone(), config);
+        self.change = change;
+        Ok(())
+    }
+}
+
+/// Next release of the repository or of a package, computed without side
+/// effects by [`release_plan`]. Versions are rendered in their scheme.
+#[derive(Debug, Clone)]
+pub struct ReleasePlan {
+    /// Name of the released package, `None` for the whole repository.
+    pub package: Option<String>,
+    /// Latest tagged version, `None` when there is none.
+    pub latest: Option<String>,
+    /// Version the release should be tagged with.
+    pub next: String,
+    /// Highest change found in the commits, raised by a dependency release
+    /// or set by an override.
+    pub change: SemVerChangeType,
+    /// Bump applied to `latest`, `change` lowered by the `pre_1_0` policy.
+    pub bump: SemVerChangeType,
+    /// CC compliant commits of the release, newest first.
+    pub commits: Vec<ConventionalCommit>,
+    /// Commits of the release that are not CC compliant, newest first.
+    pub rejected: Vec<RejectedCommit>,
+    /// Number of commits since the latest tag, compliant or not.
+    pub count: usize,
+    /// `git log` refs the commits were read from, empty for the whole history.
+    pub range: Vec<String>,
+    /// Where an overridden `next` comes from, e.g. `--release-as`.
+    pub overridden_by: Option<String>,
+    /// Dependency whose release raised `change`, see `cascade`.
+    pub raised_by: Option<String>,
+    /// Tag naming pattern of the versions.
+    pub(crate) pattern: TagPattern,
+    /// Recognizes the versions of the scheme.
+    pub(crate) parser: VersionParser,
+}
+
+/// Recognizes the versions of a versioning scheme in tag names.
+#[derive(Debug, Clone)]
+pub(crate) enum VersionParser {
+    /// A scheme implementing [`VersionScheme`], see [`is_version`].
+    Scheme(fn(&str) -> bool),
+    /// The configured `calver.format`.
+    CalVer(CalVerFormat),
+}
+
+impl VersionParser {
+    /// Returns `true` if `text` is a version of the scheme.
+    pub(crate) fn is_version(&self, text: &str) -> bool {
+        match self {
+            Self::Scheme(is_version) => is_version(text),
+            Self::CalVer(format) => format.parse(text).is_some(),
+        }
+    }
+}
+
+impl ReleasePlan {
+    /// Returns `true` if the commits call for a release.
+    pub fn is_release(&self) -> bool {
+        self.change != SemVerChangeType::None
+    }
+
+    /// Returns the name of the tag of `next`, e.g. `v1.4.0`.
+    pub fn tag_name(&self) -> String {
+        self.pattern.format(&self.next)
+    }
+
+    /// Returns the name of the tag of `latest`.
+    pub fn latest_tag_name(&self) -> Option<String> {
+        self.latest.as_ref().map(|x| self.pattern.format(x))
+    }
+}
+
+impl<V: VersionScheme> From<NextVersion<V>> for ReleasePlan {
+    fn from(next: NextVersion<V>) -> Self {
+        Self {
+            package: None,
+            latest: next.latest.map(|x| x.to_string()),
+            next: next.next.to_string(),
+            change: next.change,
+            bump: next.bump,
+            commits: next.commits,
+            rejected: next.rejected,
+            count: next.count,
+            range: next.range,
+            overridden_by: next.overridden_by,
+            raised_by: None,
+            pattern: next.pattern,
+            parser: VersionParser::Scheme(is_version::<V>),
+        }
+    }
+}
+
+/// Computes the next release of `query` in the versioning scheme of its
+/// package, or of the repository, raised to at least `min_change`.
+fn next_release(
+    query: &VersionQuery,
+    min_change: SemVerChangeType,
+    config: &Config,
+) -> anyhow::Result<ReleasePlan> {
+    fn release<V: VersionScheme>(
+        query: &VersionQuery,
+        min_change: SemVerChangeType,
+        config: &Config,
+    ) -> anyhow::Result<ReleasePlan> {
+        let mut next = next_version::<V>(query, config)?;
+        next.raise(min_change, config)?;
+        Ok(ReleasePlan {
+            package: query.package.clone(),
+            ..next.into()
+        })
+    }
+
+    match config.scheme(query.package.as_deref())? {
+        Scheme::SemVer => release::<SemVer>(query, min_change, config),
+        Scheme::Pep440 => release::<Pep440>(query, min_change, config),
+        Scheme::CalVer => calver_release(query, config),
+    }
+}
+
+/// Computes the next CalVer from today's date and the latest CalVer tag.
+///
+/// The Conventional Commits since the latest tag only decide whether a
+/// release is needed when `calver.require_bump` is set.
+///
+/// # Errors
+/// - Returns [`Err`] if a pre-release channel or `--release-as` is used.
+fn calver_release(query: &VersionQuery, config: &Config) -> anyhow::Result<ReleasePlan> {
+    if config.tag.as_ref().is_some_and(|x| x.pre_release.is_some()) {
+        return Err(anyhow::anyhow!(
+            "pre-release channels are not supported by the calver scheme"
+        ));
+    }
+    if query.release_as.is_some() {
+        return Err(anyhow::anyhow!(
+            "--release-as is not supported by the calver scheme"
+        ));
+    }
+    let format = config.calver_format()?;
+    let pattern = TagPattern::from_config(config);
+
+    let latest = list_tags(query, config)?
+        .lines()
+        .filter_map(|x| format.parse(pattern.strip(x)?))
+        .max();
+    debug!("latest CalVer tag is: {:?}", latest);
+    let range = history_range(latest.as_ref().map(|x| pattern.format(x)), query);
+    let (commits, rejected) = parse_history(range.as_deref(), None, config)?;
+    let change = commits.max_change();
+    let next = format.next(latest.as_ref(), &Date::today())?;
+
+    Ok(ReleasePlan {
+        package: None,
+        latest: latest.map(|x| x.to_string()),
+        next: next.to_string(),
+        bump: change.clone(),
+        change,
+        count: commits.len() + rejected.len(),
+        commits,
+        rejected,
+        range: range.unwrap_or_default(),
+        overridden_by: None,
+        raised_by: None,
+        pattern,
+        parser: VersionParser::CalVer(format),
+    })
+}
+
+/// Computes the next release of the repository, or of `query.package`,
+/// without printing, tagging or writing anything.
+///
+/// The release of a package is raised by the releases of the packages it
+/// depends on, see `cascade`.
+///
+/// # Arguments
+/// - `query`  – Selects the tags and the history to compute the release from.
+///
+/// - `config` – Project-level configuration used during commit parsing.
+///
+/// # Errors
+/// - Returns [`Err`] if `packages` are configured but `query.package` is not set.
+pub fn release_plan(query: &VersionQuery, config: &Config) -> anyhow::Result<ReleasePlan> {
+    match query.package {
+        None if config.packages.is_some() => Err(anyhow::anyhow!(
+            "packages are configured, select the one to release"
+        )),
+        // A release of a dependency may raise the bump of the package, even
+        // when it has no commits of its own.
+        Some(ref name) if query.release_as.is_none() => package_versions(query, config)?
+            .into_iter()
+            .find(|x| x.package.as_ref() == Some(name))
+            .ok_or(anyhow::anyhow!("no package named {}", name)),
+        _ => next_release(query, SemVerChangeType::None, config),
+    }
+}
+
+/// Computes the next release of every configured package and cascades the
+/// bumps to their dependents according to `cascade`.
+///
+/// # Arguments
+/// - `query`  – Selects the tags and the history, its `package` is ignored.
+///
+/// - `config` – Project-level configuration holding the `packages`.
+pub(crate) fn package_versions(
+    query: &VersionQuery,
+    config: &Config,
+) -> anyhow::Result<Vec<ReleasePlan>> {
+    let packages = config.packages.as_deref().unwrap_or_default();
+    let queries: Vec<VersionQuery> = packages
+        .iter()
+        .map(|x| VersionQuery {
+            package: Some(x.name.clone()),
+            ..query.clone()
+        })
+        .collect();
+    let mut releases: Vec<ReleasePlan> = Vec::new();
+    for query in &queries {
+        releases.push(next_release(query, SemVerChangeType::None, config)?);
+    }
+
+    let changes: Vec<SemVerChangeType> = releases.iter().map(|x| x.change.clone()).collect();
+    let cascaded = DependencyGraph::from_packages(packages)?
+        .cascade(&changes, config.cascade.as_ref().unwrap_or(&Cascade::Patch));
+
+    let mut result = Vec::new();
+    for ((query, next), (change, cause)) in queries.iter().zip(releases).zip(cascaded) {
+        // Only the raised releases are computed again
+        let next = match cause {
+            Some(cause) => ReleasePlan {
+                raised_by: Some(packages[cause].name.clone()),
+                ..next_release(query, change, config)?
+            },
+            None => next,
+        };
+        result.push(next);
+    }
+    Ok(result)
+}
+
+/// Builds a bold table header row from `titles`.
+fn header_row(titles: &[&str]) -> Row {
+    Row::new(
+        titles
+            .iter()
+            .map(|x| Cell::new(x).with_style(Attr::Bold))
+            .collect(),
+    )
+}
+
+/// Prints the pending release of every configured package and, when
+/// `create` is set, tags the ones with release worthy commits.
+fn tag_packages(
+    query: &VersionQuery,
+    create: bool,
+    dry_run: bool,
+    config: &Config,
+) -> anyhow::Result<()> {
+    let mut table = Table::new();
+    table.add_row(header_row(&["Package", "Latest", "Bump", "Next"]));
+
+    let releases = package_versions(query, config)?;
+    for release in &releases {
+        let bump = match release.raised_by {
+            Some(ref cause) => format!("{:?} (via {})", release.change, cause),
+            None => format!("{:?}", release.change),
+        };
+        table.add_row(Row::new(vec![
+            Cell::new(release.package.as_deref().unwrap_or_default()),
+            Cell::new(&release.latest_tag_name().unwrap_or("-".to_owned())),
+            Cell::new(&bump),
+            Cell::new(&release.tag_name()),
+        ]));
+    }
+    table.printstd();
+
+    if create {
+        let releases: Vec<&ReleasePlan> = releases.iter().filter(|x| x.is_release()).collect();
+        for next in &releases {
+            ensure_head_untagged(&next.pattern, |x| next.parser.is_version(x))?;
+        }
+        for next in releases {
+            create_tag(&next.next, &next.commits, &next.pattern, dry_run, config)?;
+        }
+    }
+    Ok(())
+}
+
+/// Prints how the next version of `plan` was found, with the `pre_1_0`
+/// policy of `config` lowering its bump.
+fn render_plan(plan: &ReleasePlan, config: &Config) {
+    if !plan.rejected.is_empty() {
+        println!("Commits not CC compliant, ignored:");
+        error_table(&plan.rejected, DEFAULT_WRAP_WIDTH).printstd();
+    }
+    let change = plan.commits.max_change();
+    println!("Change type is: {:?}", change);
+    println!(
+        "Latest identified tag is: {}",
+        plan.latest.as_deref().unwrap_or("None")
+    );
+    if let Some(ref cause) = plan.raised_by {
+        println!(
+            "Change type raised to {:?} by dependency {}",
+            plan.change, cause
+        );
+    }
+    match plan.overridden_by {
+        Some(ref source) => println!(
+            "Next version {} comes from an override, {}, instead of the {:?} change",
+            plan.next, source, change
+        ),
+        None if plan.bump != plan.change => println!(
+            "Bump is lowered to {:?} while the major version is 0 (pre_1_0: {})",
+            plan.bump,
+            config.pre_1_0.clone().unwrap_or_default()
+        ),
+        None => {}
+    }
+    println!("Next tag is {}", plan.next);
+}
+
+/// Computes the next version with [`release_plan`], prints how it was found
+/// and optionally tags HEAD with it.
+///
+/// When `packages` are configured and `query.package` is `None`, a table of
+/// the pending release of every package is printed instead. With the
+/// `calver` scheme, commits are only required when `calver.require_bump` is
+/// set.
+///
+/// # Arguments
+/// - `query`   – Selects the tags and the history to compute the version from.
+///
+/// - `create`  – Create an annotated tag for the next version on HEAD.
+///
+/// - `dry_run` – Only report the tag that would be created.
+///
+/// - `config`  – Project-level configuration used during commit parsing.
+pub fn tag(
+    query: VersionQuery,
+    create: bool,
+    dry_run: bool,
+    config: &Config,
+) -> anyhow::Result<()> {
+    if query.package.is_none() && config.packages.is_some() {
+        if query.release_as.is_some() {
+            return Err(anyhow::anyhow!(
+                "--release-as needs --package when packages are configured"
+            ));
+        }
+        return tag_packages(&query, create, dry_run, config);
+    }
+
+    let plan = release_plan(&query, config)?;
+    render_plan(&plan, config);
+
+    if create {
+        ensure_head_untagged(&plan.pattern, |x| plan.parser.is_version(x))?;
+        let require_bump = match config.scheme(query.package.as_deref())? {
+            Scheme::CalVer => config
+                .calver
+                .as_ref()
+                .and_then(|x| x.require_bump)
+                .unwrap_or_default(),
+            _ => true,
+        };
+        if require_bump {
+            ensure_release_worthy(&plan.change, &plan.next)?;
+        }
+        create_tag(&plan.next, &plan.commits, &plan.pattern, dry_run, config)?;
+    }
 
     Ok(())
 }
@@ -206,14 +829,21 @@ pub fn tag(
 mod tests {
     use std::vec;
 
-    use crate::config::{Config, Tag};
+    use crate::config::{Config, PreStable, Tag};
+    use crate::utils::pep440::Pep440;
     use crate::utils::semver::SemVer;
+    use crate::utils::tag_pattern::TagPattern;
 
-    use crate::command::tag::{latest_tag, parse_tags};
+    use crate::command::tag::{
+        is_version, latest_tag, next_prerelease, parse_tags, release_as, release_override,
+        render_tag_message, version_for, VersionParser, VersionQuery,
+    };
+    use crate::utils::ConventionalCommit;
+    use crate::utils::SemVerChangeType;
     #[test]
     fn test_parse() {
         let control = "0.1.0\ntest";
-        let tags = parse_tags(control);
+        let tags: Vec<SemVer> = parse_tags(control, &TagPattern::default());
         let result = SemVer {
             major: 0,
             minor: 1,
@@ -225,17 +855,29 @@ mod tests {
         assert!(tags[0] == result);
     }
     #[test]
+    fn test_parse_prefixed() {
+        let control = "v0.1.0\n0.2.0\nv0.1.1-rc.1";
+        let tags: Vec<SemVer> = parse_tags(control, &TagPattern::new("v", None));
+        assert_eq!(
+            tags,
+            vec![
+                "0.1.0".parse::<SemVer>().unwrap(),
+                "0.1.1-rc.1".parse::<SemVer>().unwrap()
+            ]
+        );
+    }
+    #[test]
     fn test_parse_no_tags() {
         let control = "test\nababa\nnono";
-        let tags = parse_tags(control);
+        let tags: Vec<SemVer> = parse_tags(control, &TagPattern::default());
         assert!(tags.is_empty());
         assert!(tags.iter().max().is_none());
         let control = "";
-        let tags = parse_tags(control);
+        let tags: Vec<SemVer> = parse_tags(control, &TagPattern::default());
         assert!(tags.is_empty());
         assert!(tags.iter().max().is_none());
         let control = "0.0.0";
-        let tags = parse_tags(control);
+        let tags: Vec<SemVer> = parse_tags(control, &TagPattern::default());
         assert!(tags.is_empty());
         assert!(tags.iter().max().is_none());
     }
@@ -260,11 +902,7 @@ mod tests {
         ];
 
         assert_eq!(&test_vec[1], latest_tag(&test_vec, false, &config).unwrap());
-        config.tag = Some(Tag {
-            ignore_prereleases: None,
-            merged: None,
-            no_merged: None,
-        });
+        config.tag = Some(Tag::default());
         assert_eq!(&test_vec[1], latest_tag(&test_vec, false, &config).unwrap());
 
         test_vec.push(SemVer {
@@ -278,15 +916,138 @@ mod tests {
         assert_eq!(&test_vec[1], latest_tag(&test_vec, true, &config).unwrap());
         config.tag = Some(Tag {
             ignore_prereleases: Some(true),
-            merged: None,
-            no_merged: None,
+            ..Tag::default()
         });
         assert_eq!(&test_vec[1], latest_tag(&test_vec, false, &config).unwrap());
         config.tag = Some(Tag {
             ignore_prereleases: Some(false),
-            merged: None,
-            no_merged: None,
+            ..Tag::default()
         });
         assert_eq!(&test_vec[2], latest_tag(&test_vec, false, &config).unwrap());
     }
+    #[test]
+    fn tag_message() {
+        let config = Config::default();
+        let commits = vec![
+            ConventionalCommit::new("feat(cli): new flag", &config, "bbb".to_owned()).unwrap(),
+            ConventionalCommit::new("fix!: breaking fix", &config, "aaa".to_owned()).unwrap(),
+        ];
+        let version = "1.2.0".parse::<SemVer>().unwrap();
+        assert_eq!(
+            render_tag_message("v{version}\n\n{summary}", &version, &commits),
+            "v1.2.0\n\n- feat(cli): new flag (bbb)\n- fix!: breaking fix (aaa)"
+        );
+        assert_eq!(render_tag_message("{version}", &version, &[]), "1.2.0");
+    }
+    fn versions(to_parse: &[&str]) -> Vec<SemVer> {
+        to_parse
+            .iter()
+            .map(|x| x.parse::<SemVer>().unwrap())
+            .collect()
+    }
+    #[test]
+    fn prerelease_from_stable() {
+        let tags = versions(&["1.1.0", "1.2.0"]);
+        let next = next_prerelease(&tags[1], &tags, SemVerChangeType::Minor, "rc").unwrap();
+        assert_eq!(next, "1.3.0-rc.1".parse::<SemVer>().unwrap());
+    }
+    #[test]
+    fn prerelease_counter() {
+        let tags = versions(&["1.2.0", "1.3.0-rc.1"]);
+        for change in [SemVerChangeType::Patch, SemVerChangeType::Minor] {
+            let next = next_prerelease(&tags[1], &tags, change, "rc").unwrap();
+            assert_eq!(next, "1.3.0-rc.2".parse::<SemVer>().unwrap());
+        }
+        let next = next_prerelease(&tags[1], &tags, SemVerChangeType::None, "rc").unwrap();
+        assert_eq!(next, tags[1]);
+    }
+    #[test]
+    fn prerelease_level_up() {
+        let tags = versions(&["1.2.0", "1.3.0-rc.1", "1.3.0-rc.2"]);
+        let next = next_prerelease(&tags[2], &tags, SemVerChangeType::Major, "rc").unwrap();
+        assert_eq!(next, "2.0.0-rc.1".parse::<SemVer>().unwrap());
+    }
+    #[test]
+    fn prerelease_channel_switch() {
+        let tags = versions(&["1.2.0", "1.3.0-beta.3", "1.3.0-rc.1"]);
+        let next = next_prerelease(&tags[1], &tags, SemVerChangeType::Patch, "rc").unwrap();
+        assert_eq!(next, "1.3.0-rc.2".parse::<SemVer>().unwrap());
+        let tags = versions(&["1.2.0", "1.3.0-beta.3"]);
+        let next = next_prerelease(&tags[1], &tags, SemVerChangeType::Patch, "rc").unwrap();
+        assert_eq!(next, "1.3.0-rc.1".parse::<SemVer>().unwrap());
+    }
+    #[test]
+    fn prerelease_pep440() {
+        let tags: Vec<Pep440> = parse_tags("1.3.0.post1\n1.4.0rc2\nv2", &TagPattern::default());
+        let next = next_prerelease(&tags[1], &tags, SemVerChangeType::Minor, "rc").unwrap();
+        assert_eq!(next.to_string(), "1.4.0rc3");
+        let next = next_prerelease(&tags[1], &tags, SemVerChangeType::Major, "b").unwrap();
+        assert_eq!(next.to_string(), "2.0.0b1");
+    }
+    #[test]
+    fn pre_1_0_shift() {
+        let config = Config {
+            pre_1_0: Some(PreStable::Shift),
+            ..Config::default()
+        };
+        let next = |latest: &str, change| {
+            let latest = latest.parse::<SemVer>().unwrap();
+            version_for(&latest, &[], change, 1, &config)
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(next("0.4.2", SemVerChangeType::Major), "0.5.0");
+        assert_eq!(next("0.4.2", SemVerChangeType::Minor), "0.4.3");
+        assert_eq!(next("1.4.2", SemVerChangeType::Major), "2.0.0");
+        assert_eq!(config.pre_1_0.as_ref().unwrap().to_string(), "shift");
+        let latest = "0.4.2".parse::<SemVer>().unwrap();
+        assert!(release_as("1.0.0", "--release-as", Some(&latest)).is_ok());
+        assert!(release_as("0.4.2", "--release-as", Some(&latest)).is_err());
+    }
+    #[test]
+    fn version_parser() {
+        let parser = VersionParser::Scheme(is_version::<SemVer>);
+        assert!(parser.is_version("1.2.0"));
+        assert!(!parser.is_version("2026.10"));
+        let parser = VersionParser::CalVer("YYYY.0M.MICRO".parse().unwrap());
+        assert!(parser.is_version("2026.10.0"));
+        assert!(!parser.is_version("1.2.0"));
+    }
+    #[test]
+    fn release_as_footer() {
+        let commits: Vec<ConventionalCommit> = [
+            "fix: patch",
+            "feat: launch\n\nbody\n\nRefs: #12\nrelease-as: 2.0.0",
+            "feat: older\n\nbody\n\nRelease-As: 1.9.0",
+        ]
+        .iter()
+        .map(|x| ConventionalCommit::new(x, &Config::default(), "abc".to_owned()).unwrap())
+        .collect();
+        let query = VersionQuery::default();
+        let (text, source) = release_override(&query, &commits).unwrap();
+        assert_eq!(text, "2.0.0");
+        assert_eq!(source, "the Release-As footer of abc");
+
+        let query = VersionQuery {
+            release_as: Some("3.0.0".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(release_override(&query, &commits).unwrap().0, "3.0.0");
+        assert!(release_override(&query, &commits[..1]).is_some());
+        assert!(release_override(&VersionQuery::default(), &commits[..1]).is_none());
+
+        // A footer right after the header, without a body paragraph
+        let commits = [ConventionalCommit::new(
+            "chore: release 2.0.0\n\nRelease-As: 2.0.0\n",
+            &Config::default(),
+            "def".to_owned(),
+        )
+        .unwrap()];
+        assert_eq!(
+            release_override(&VersionQuery::default(), &commits)
+                .unwrap()
+                .0,
+            "2.0.0"
+        );
+    }
 }
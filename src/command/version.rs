@@ -0,0 +1,202 @@
+use crate::command::tag::{release_plan, ReleasePlan, VersionQuery};
+use crate::config::{Config, Scheme};
+use crate::utils::build_meta::{self, BuildInfo};
+use crate::utils::pep440::Pep440;
+use crate::utils::semver::SemVer;
+use crate::utils::version::VersionScheme;
+use crate::utils::SemVerChangeType;
+use log::warn;
+
+/// Build metadata of development versions when `tag.build_meta` is not set.
+const DEFAULT_DEV_BUILD_META: &str = "g{sha}";
+
+/// Computes the development version of HEAD, e.g. `1.4.0-dev.12+gabc1234`.
+///
+/// `1.4.0` is the predicted next version (at least a patch bump, so that the
+/// snapshot sorts after the latest tag), `12` the number of commits since the
+/// latest tag and `abc1234` the short SHA of HEAD. When HEAD is the latest tag
+/// itself, the tag is returned unchanged.
+///
+/// # Errors
+/// - Returns [`Err`] if the latest version of `plan` is not a `V` version.
+fn dev_version<V: VersionScheme>(plan: &ReleasePlan, build_meta: &str) -> anyhow::Result<V> {
+    let latest = match plan.latest {
+        Some(ref latest) => V::parse(latest).ok_or(anyhow::anyhow!(
+            "{:?} is not a version of the scheme",
+            latest
+        ))?,
+        None => V::initial(),
+    };
+    if plan.count == 0 {
+        return Ok(latest);
+    }
+    Ok(latest
+        .bump(plan.bump.clone().max(SemVerChangeType::Patch))
+        .with_dev(plan.count as u128)
+        .with_local(build_meta))
+}
+
+/// Value printed by the [`version`] command.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum VersionOutput {
+    /// The next version.
+    #[default]
+    Next,
+    /// A `describe`-style development version, see [`dev_version`].
+    Dev,
+    /// The latest tagged version, nothing when there is none.
+    Current,
+    /// The bump of the next release: `none`, `patch`, `minor` or `major`.
+    Bump,
+    /// Every value above as a JSON object.
+    Json,
+}
+
+/// Prints the requested value, and nothing else, to stdout. Diagnostics are
+/// logged to stderr.
+///
+/// The values are the ones of [`release_plan`], so the release of a package
+/// is raised by the releases of its dependencies like with `tag`.
+///
+/// # Arguments
+/// - `output`             – Value to print, see [`VersionOutput`].
+///
+/// - `package`            – Name of the `packages` entry to version, required when packages are configured.
+///
+/// - `ignore_prereleases` – Skip pre-release tags when looking for the latest tag.
+///
+/// - `config`             – Project-level configuration used during commit parsing.
+///
+/// # Errors
+/// - Returns [`Err`] if `packages` are configured but `package` is not set.
+/// - Returns [`Err`] for a development version of the `calver` scheme, the
+///   `dev` value of the JSON object is `null` instead.
+pub fn version(
+    output: VersionOutput,
+    package: Option<String>,
+    ignore_prereleases: bool,
+    config: &Config,
+) -> anyhow::Result<()> {
+    let query = VersionQuery {
+        ignore_prereleases,
+        package,
+        ..Default::default()
+    };
+    let plan = release_plan(&query, config)?;
+    let dev = match output {
+        VersionOutput::Dev | VersionOutput::Json => {
+            let template = config
+                .tag
+                .as_ref()
+                .and_then(|x| x.build_meta.as_deref())
+                .unwrap_or(DEFAULT_DEV_BUILD_META);
+            let build_meta = build_meta::render(template, &BuildInfo::collect(plan.count)?)?;
+            match config.scheme(query.package.as_deref())? {
+                Scheme::SemVer => Some(dev_version::<SemVer>(&plan, &build_meta)?.to_string()),
+                Scheme::Pep440 => Some(dev_version::<Pep440>(&plan, &build_meta)?.to_string()),
+                Scheme::CalVer if output == VersionOutput::Dev => {
+                    return Err(anyhow::anyhow!(
+                        "development versions are not supported by the calver scheme"
+                    ))
+                }
+                Scheme::CalVer => None,
+            }
+        }
+        _ => None,
+    };
+
+    match output {
+        VersionOutput::Next => println!("{}", plan.next),
+        VersionOutput::Dev => println!("{}", dev.unwrap_or_default()),
+        VersionOutput::Current => match plan.latest {
+            Some(ref latest) => println!("{}", latest),
+            None => warn!("no tag found, there is no current version"),
+        },
+        VersionOutput::Bump => println!("{}", plan.bump),
+        VersionOutput::Json => {
+            let json = serde_json::json!({
+                "current": plan.latest,
+                "next": plan.next,
+                "bump": plan.bump.to_string(),
+                "dev": dev,
+            });
+            println!("{}", serde_json::to_string_pretty(&json)?);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dev_version, version, VersionOutput};
+    use crate::command::tag::{ReleasePlan, VersionParser};
+    use crate::config::{Config, Package};
+    use crate::utils::pep440::Pep440;
+    use crate::utils::semver::SemVer;
+    use crate::utils::tag_pattern::TagPattern;
+    use crate::utils::SemVerChangeType;
+
+    fn next(latest: Option<&str>, change: SemVerChangeType, count: usize) -> ReleasePlan {
+        ReleasePlan {
+            package: None,
+            latest: latest.map(str::to_owned),
+            next: String::new(),
+            bump: change.clone(),
+            change,
+            commits: Vec::new(),
+            rejected: Vec::new(),
+            count,
+            range: Vec::new(),
+            overridden_by: None,
+            raised_by: None,
+            pattern: TagPattern::default(),
+            parser: VersionParser::Scheme(|_| true),
+        }
+    }
+
+    #[test]
+    fn dev() {
+        let version = dev_version::<SemVer>(
+            &next(Some("1.3.2"), SemVerChangeType::Minor, 12),
+            "gabc1234",
+        );
+        assert_eq!(version.unwrap().to_string(), "1.4.0-dev.12+gabc1234");
+        let version = dev_version::<Pep440>(
+            &next(Some("1.3.2"), SemVerChangeType::Minor, 12),
+            "gabc1234",
+        );
+        assert_eq!(version.unwrap().to_string(), "1.4.0.dev12+gabc1234");
+    }
+
+    #[test]
+    fn dev_without_release_worthy_commits() {
+        let version = dev_version::<SemVer>(&next(Some("1.3.2"), SemVerChangeType::None, 2), "g1");
+        assert_eq!(version.unwrap().to_string(), "1.3.3-dev.2+g1");
+        let version = dev_version::<SemVer>(&next(None, SemVerChangeType::None, 2), "g1");
+        assert_eq!(version.unwrap().to_string(), "0.0.1-dev.2+g1");
+    }
+
+    #[test]
+    fn dev_on_tag() {
+        let version = dev_version::<SemVer>(&next(Some("1.3.2"), SemVerChangeType::None, 0), "g1");
+        assert_eq!(version.unwrap().to_string(), "1.3.2");
+        assert!(dev_version::<SemVer>(&next(Some("v1"), SemVerChangeType::None, 0), "g1").is_err());
+    }
+
+    /// With packages configured, the package to version must be selected.
+    #[test]
+    fn packaged_config() {
+        let config = Config {
+            packages: Some(vec![Package {
+                name: "core".to_owned(),
+                path: "core".to_owned(),
+                tag_prefix: None,
+                scopes: None,
+                scheme: None,
+            }]),
+            ..Default::default()
+        };
+        let error = version(VersionOutput::Next, None, false, &config).unwrap_err();
+        assert!(error.to_string().contains("select the one to release"));
+    }
+}
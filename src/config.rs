@@ -1,5 +1,12 @@
+use crate::utils::calver::{CalVerFormat, DEFAULT_CALVER_FORMAT};
+use crate::utils::cc::footer_value;
+use crate::utils::errors::ConventionalCommitParseError;
+use crate::utils::tag_pattern::PACKAGE_PLACEHOLDER;
+use crate::utils::SemVerChangeType;
 use colored::Colorize;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Top-level configuration for the versioning tool.
 ///
@@ -22,10 +29,404 @@ pub struct Config {
 
     /// Optional settings that control how Git tags are resolved.
     pub tag: Option<Tag>,
+
+    /// Optional files whose version is rewritten by the `bump` command.
+    /// Not supported with `packages`, whose `Cargo.toml` files are bumped.
+    pub bump_files: Option<Vec<BumpFile>>,
+
+    /// Optional per-branch release channels. The first entry whose `pattern`
+    /// matches the current branch overrides `tag.pre_release` and `tag.filter`.
+    pub branches: Option<Vec<Branch>>,
+
+    /// Optional independently versioned packages of a monorepo.
+    pub packages: Option<Vec<Package>>,
+
+    /// How a package release cascades to the packages depending on it, read
+    /// from their manifests. Defaults to `patch`.
+    pub cascade: Option<Cascade>,
+
+    /// Versioning scheme of the tags. Defaults to `semver`.
+    pub scheme: Option<Scheme>,
+
+    /// Optional settings of the `calver` scheme.
+    pub calver: Option<CalVerOptions>,
+
+    /// How changes are bumped while the major version is 0. Defaults to
+    /// `keep`.
+    pub pre_1_0: Option<PreStable>,
+
+    /// Optional ordered rules mapping commits to a change, the first match
+    /// wins. Commits matching no rule fall back to `minor_trigger` and
+    /// `patch_trigger`. Breaking changes are always `major`.
+    pub bump_rules: Option<Vec<BumpRule>>,
+
+    /// Optional lint rules checked by `check` on top of the Conventional
+    /// Commits structure, all `off` when unset.
+    pub rules: Option<Rules>,
+
+    /// Optional scopes accepted in the commits and the types requiring or
+    /// forbidding one. Commits breaking them are not Conventional Commits.
+    pub scopes: Option<Scopes>,
 }
 
-/// Controls which Git tags are considered when determining the latest version.
+/// A rule of `bump_rules`, matching the commits meeting every set criterion.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BumpRule {
+    /// Commit type, e.g. `fix`. Any type matches when unset.
+    #[serde(rename = "type")]
+    pub commit_type: Option<String>,
+
+    /// Commit scope, e.g. `deps`. Any scope, or none, matches when unset.
+    pub scope: Option<String>,
+
+    /// Footer token the commit must hold, e.g. `Security` for
+    /// `Security: CVE-2026-1234` or `Security #12`.
+    pub footer: Option<String>,
+
+    /// Change of the matching commits: `none`, `patch`, `minor` or `major`.
+    pub change: SemVerChangeType,
+}
+
+impl BumpRule {
+    /// Returns `true` if a commit of `commit_type`, `scope` and `footer`
+    /// meets every criterion of the rule.
+    pub fn matches(&self, commit_type: &str, scope: Option<&str>, footer: Option<&str>) -> bool {
+        let token = |token: &str| footer.is_some_and(|x| footer_value(x, token).is_some());
+        self.commit_type.as_ref().is_none_or(|x| x == commit_type)
+            && self
+                .scope
+                .as_ref()
+                .is_none_or(|x| Some(x.as_str()) == scope)
+            && self.footer.as_deref().is_none_or(token)
+    }
+}
+
+/// Severity of a lint rule.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The rule is not checked.
+    #[default]
+    Off,
+    /// Violations are reported but the commit is accepted.
+    Warn,
+    /// Violations reject the commit.
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Off => write!(f, "off"),
+            Self::Warn => write!(f, "warn"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Letter case required by the `subject_case` and `type_case` rules.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Case {
+    /// No uppercase letter, e.g. `add flag`.
+    Lower,
+    /// No lowercase letter, e.g. `ADD FLAG`.
+    Upper,
+    /// Starts with an uppercase letter, e.g. `Add flag`.
+    Sentence,
+}
+
+impl Case {
+    /// Returns `true` if `text` is written in this case.
+    pub fn matches(&self, text: &str) -> bool {
+        match self {
+            Self::Lower => !text.chars().any(char::is_uppercase),
+            Self::Upper => !text.chars().any(char::is_lowercase),
+            Self::Sentence => text.chars().next().is_some_and(char::is_uppercase),
+        }
+    }
+}
+
+impl std::fmt::Display for Case {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Lower => write!(f, "lower case"),
+            Self::Upper => write!(f, "upper case"),
+            Self::Sentence => write!(f, "sentence case"),
+        }
+    }
+}
+
+/// A lint rule of `rules`, checked with `severity` against its optional
+/// `value`. The default value of the rule applies when it is unset.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Rule<T = ()> {
+    /// Severity of the violations: `off`, `warn` or `error`.
+    pub severity: Severity,
+
+    /// Parameter of the rule, e.g. the maximum length of `header_max_length`.
+    pub value: Option<T>,
+}
+
+/// Lint rules of the commit messages, see [`crate::utils::lint`]. Required
+/// scopes are configured with `scopes.types`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Rules {
+    /// Maximum length of the header. Defaults to 72.
+    pub header_max_length: Option<Rule<usize>>,
+
+    /// Case of the description: `lower`, `upper` or `sentence`. Defaults to
+    /// `lower`.
+    pub subject_case: Option<Rule<Case>>,
+
+    /// Characters the description must not end with. Defaults to `.`.
+    pub subject_full_stop: Option<Rule<String>>,
+
+    /// A blank line separates the header from the body.
+    pub body_leading_blank: Option<Rule>,
+
+    /// Maximum length of the lines following the header. Defaults to 100.
+    pub body_max_line_length: Option<Rule<usize>>,
+
+    /// Case of the type: `lower`, `upper` or `sentence`. Defaults to `lower`.
+    pub type_case: Option<Rule<Case>>,
+}
+
+/// Whether the commits of a type have a scope.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScopePolicy {
+    /// Commits must have a scope.
+    Required,
+    /// Commits must not have a scope.
+    Forbidden,
+    /// Commits may have a scope.
+    Optional,
+}
+
+/// Scopes accepted in the Conventional Commits.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Scopes {
+    /// Allowed scopes, e.g. `cli` or `parser`.
+    pub allowed: Option<Vec<String>>,
+
+    /// Regular expressions the whole scope may match instead, e.g.
+    /// `deps(-dev)?`.
+    pub patterns: Option<Vec<String>>,
+
+    /// Scope policy of the types, e.g. `feat: required` and
+    /// `ci: forbidden`. Types not listed are `optional`.
+    pub types: Option<BTreeMap<String, ScopePolicy>>,
+}
+
+impl Scopes {
+    /// Returns `true` if `scope` is allowed. Any scope is when neither
+    /// `allowed` nor `patterns` is set.
+    pub fn is_allowed(&self, scope: &str) -> bool {
+        if self.allowed.is_none() && self.patterns.is_none() {
+            return true;
+        }
+        self.allowed.iter().flatten().any(|x| x == scope)
+            || self
+                .patterns
+                .iter()
+                .flatten()
+                .any(|x| Regex::new(&format!("^(?:{})$", x)).is_ok_and(|x| x.is_match(scope)))
+    }
+
+    /// Checks the `scope` of a commit of `commit_type`.
+    ///
+    /// # Errors
+    /// - Returns [`Err`] if `commit_type` requires a scope and there is none.
+    /// - Returns [`Err`] if `commit_type` forbids a scope and there is one.
+    /// - Returns [`Err`] if `scope` is not allowed, listing the allowed ones.
+    pub fn check(
+        &self,
+        commit_type: &str,
+        scope: Option<&str>,
+    ) -> Result<(), ConventionalCommitParseError> {
+        let policy = self.types.as_ref().and_then(|x| x.get(commit_type));
+        match (policy, scope) {
+            (Some(ScopePolicy::Required), None) => {
+                Err(ConventionalCommitParseError::MissingScope {
+                    commit_type: commit_type.to_owned(),
+                    expected: self.expected(),
+                })
+            }
+            (Some(ScopePolicy::Forbidden), Some(scope)) => {
+                Err(ConventionalCommitParseError::UnexpectedScope {
+                    commit_type: commit_type.to_owned(),
+                    found: scope.to_owned(),
+                })
+            }
+            (_, Some(scope)) if !self.is_allowed(scope) => {
+                Err(ConventionalCommitParseError::InvalidScope {
+                    expected: self.expected(),
+                    found: scope.to_owned(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the allowed scopes followed by the patterns, written `/pattern/`.
+    fn expected(&self) -> Vec<String> {
+        let patterns = self.patterns.iter().flatten().map(|x| format!("/{}/", x));
+        self.allowed
+            .iter()
+            .flatten()
+            .cloned()
+            .chain(patterns)
+            .collect()
+    }
+}
+
+/// Bump policy of the versions below 1.0.0.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PreStable {
+    /// Changes are bumped as after 1.0.0, a breaking change releases 1.0.0.
+    #[default]
+    Keep,
+    /// Breaking changes bump the minor version and features the patch one,
+    /// 1.0.0 is only released with `--release-as`.
+    Shift,
+}
+
+impl PreStable {
+    /// Returns the bump applied for a `change` while the major version is 0.
+    pub fn change(&self, change: SemVerChangeType) -> SemVerChangeType {
+        match (self, change) {
+            (Self::Shift, SemVerChangeType::Major) => SemVerChangeType::Minor,
+            (Self::Shift, SemVerChangeType::Minor) => SemVerChangeType::Patch,
+            (_, change) => change,
+        }
+    }
+}
+
+impl std::fmt::Display for PreStable {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Keep => write!(f, "keep"),
+            Self::Shift => write!(f, "shift"),
+        }
+    }
+}
+
+/// Versioning scheme of the tags.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scheme {
+    /// Semantic Versioning, bumped by the Conventional Commits.
+    SemVer,
+    /// Calendar Versioning, set from the release date.
+    CalVer,
+    /// Python package versions, bumped by the Conventional Commits.
+    Pep440,
+}
+
+/// Settings of the `calver` scheme.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CalVerOptions {
+    /// Dot separated format of the versions, made of `YYYY`, `YY`, `0Y`,
+    /// `MM`, `0M`, `DD`, `0D` and a final `MICRO`. Defaults to `YYYY.0M.MICRO`.
+    pub format: Option<String>,
+
+    /// Only release when the Conventional Commits since the latest tag call
+    /// for a bump. Defaults to `false`, releasing any new commit.
+    pub require_bump: Option<bool>,
+}
+
+/// A package of a monorepo, versioned from the commits touching its `path`.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Package {
+    /// Name of the package, substituted for `{package}` in `tag_prefix`.
+    pub name: String,
+
+    /// Directory of the package, only commits touching it are considered.
+    pub path: String,
+
+    /// Tag prefix or pattern of the package, see `tag.prefix`.
+    /// Defaults to `{package}@{version}`.
+    pub tag_prefix: Option<String>,
+
+    /// When set, only commits with one of these scopes are considered.
+    pub scopes: Option<Vec<String>>,
+
+    /// Versioning scheme of the package tags, see `scheme`.
+    pub scheme: Option<Scheme>,
+}
+
+impl Package {
+    /// Returns `true` if a commit with the given `scope` belongs to the package.
+    pub fn has_scope(&self, scope: Option<&str>) -> bool {
+        match self.scopes {
+            Some(ref scopes) => scope.is_some_and(|x| scopes.iter().any(|s| s == x)),
+            None => true,
+        }
+    }
+}
+
+/// Bump given to the dependents of a released package.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Cascade {
+    /// Dependents are only released for their own commits.
+    None,
+    /// Dependents get at least a patch bump.
+    Patch,
+    /// Dependents get at least the bump of their dependency.
+    Match,
+}
+
+/// Release channel used when running on the branches matching `pattern`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Branch {
+    /// Branch name, `*` matches any sequence of characters (e.g. `release/*`).
+    pub pattern: String,
+
+    /// Pre-release channel of the versions computed on these branches
+    /// (e.g. `beta`). Versions are stable when unset.
+    pub pre_release: Option<String>,
+
+    /// Only tags matching this `git tag -l` pattern are considered
+    /// (e.g. `*-beta.*`).
+    pub filter: Option<String>,
+}
+
+/// A file holding the project version, rewritten in place on release.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BumpFile {
+    /// Path of the file, relative to the directory ruvex runs in.
+    pub path: String,
+
+    /// How the version is stored in the file.
+    pub kind: BumpFileKind,
+
+    /// Regex locating the version when `kind` is `regex`. The version must be
+    /// captured by a group named `version` (e.g. `VERSION = "(?P<version>[^"]+)"`).
+    pub pattern: Option<String>,
+}
+
+/// Supported version file formats.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BumpFileKind {
+    /// `Cargo.toml`, `[package] version` or `[workspace.package] version`.
+    /// A workspace root also releases its members and `Cargo.lock` in step.
+    Cargo,
+    /// `package.json`, top-level `"version"`.
+    Npm,
+    /// `pyproject.toml`, `[project] version` or `[tool.poetry] version`.
+    Pyproject,
+    /// A file containing nothing but the version.
+    Plain,
+    /// Any file, the version is located with `pattern`.
+    Regex,
+}
+
+/// Controls which Git tags are considered when determining the latest version.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct Tag {
     /// If set, only tags reachable from this merged branch are considered.
     pub merged: Option<String>,
@@ -36,6 +437,31 @@ pub struct Tag {
     /// When `true`, pre-release tags (e.g. `v1.0.0-rc.1`) are excluded
     /// from the latest-version lookup.
     pub ignore_prereleases: Option<bool>,
+
+    /// Message template of the annotated tag written by `tag --create`.
+    /// `{version}` is replaced by the new version and `{summary}` by the
+    /// list of commits included in the release.
+    pub message: Option<String>,
+
+    /// Tag name prefix (e.g. `v` for `v1.2.3`) or pattern containing
+    /// `{version}` and optionally `{package}` (e.g. `{package}@{version}`).
+    /// Stripped when reading tags and added back when writing them.
+    pub prefix: Option<String>,
+
+    /// Package name substituted for `{package}` in `prefix`.
+    pub package: Option<String>,
+
+    /// Pre-release channel (e.g. `rc`). When set, `tag` computes
+    /// `<version>-<channel>.<n>` versions, increasing `n` on every release.
+    pub pre_release: Option<String>,
+
+    /// Only tags matching this `git tag -l` pattern are considered
+    /// (e.g. `v*`).
+    pub filter: Option<String>,
+
+    /// Build metadata template appended to computed versions after `+`,
+    /// e.g. `g{sha}`. Supports `{sha}`, `{date}`, `{count}` and `{env:NAME}`.
+    pub build_meta: Option<String>,
 }
 
 /// Optional CI check configuration for validating commit content.
@@ -104,20 +530,188 @@ impl Config {
             .context("".red().to_string()));
         }
 
+        let rule_types: Vec<String> = self
+            .bump_rules
+            .iter()
+            .flatten()
+            .filter_map(|x| x.commit_type.clone())
+            .collect();
+        if !all_in_cc_types(&rule_types) {
+            return Err(anyhow::anyhow!(
+                "\nConfig Error:\nall types of bump_rules {:?} must be included in cc_types {:?}",
+                rule_types,
+                self.cc_types,
+            )
+            .context("".red().to_string()));
+        }
+
+        if let Some(ref scopes) = self.scopes {
+            if let Some(pattern) = scopes
+                .patterns
+                .iter()
+                .flatten()
+                .find(|x| Regex::new(x).is_err())
+            {
+                return Err(anyhow::anyhow!(
+                    "\nConfig Error:\nscopes.patterns {:?} is not a valid regex",
+                    pattern,
+                )
+                .context("".red().to_string()));
+            }
+            let policy_types: Vec<String> = scopes
+                .types
+                .iter()
+                .flat_map(|x| x.keys().cloned())
+                .collect();
+            if !all_in_cc_types(&policy_types) {
+                return Err(anyhow::anyhow!(
+                    "\nConfig Error:\nall types of scopes.types {:?} must be included in cc_types {:?}",
+                    policy_types,
+                    self.cc_types,
+                )
+                .context("".red().to_string()));
+            }
+        }
+
+        if let Some(ref tag) = self.tag {
+            if tag
+                .prefix
+                .as_ref()
+                .is_some_and(|x| x.contains(PACKAGE_PLACEHOLDER))
+                && tag.package.is_none()
+            {
+                return Err(anyhow::anyhow!(
+                    "\nConfig Error:\ntag.prefix {:?} uses {} but tag.package is not set",
+                    tag.prefix.as_ref().unwrap(),
+                    PACKAGE_PLACEHOLDER,
+                )
+                .context("".red().to_string()));
+            }
+        }
+
+        let package_schemes = self.packages.iter().flatten().map(|x| x.scheme.as_ref());
+        if package_schemes.flatten().any(|x| *x == Scheme::CalVer) {
+            return Err(anyhow::anyhow!(
+                "\nConfig Error:\nthe calver scheme is not supported by packages"
+            )
+            .context("".red().to_string()));
+        }
+        if self.scheme == Some(Scheme::CalVer) {
+            if self.packages.is_some() {
+                return Err(anyhow::anyhow!(
+                    "\nConfig Error:\nthe calver scheme does not support packages"
+                )
+                .context("".red().to_string()));
+            }
+            if let Err(err) = self.calver_format() {
+                return Err(
+                    anyhow::anyhow!("\nConfig Error:\ninvalid calver.format: {}", err)
+                        .context("".red().to_string()),
+                );
+            }
+        }
+
+        if self.packages.is_some() && self.bump_files.is_some() {
+            return Err(anyhow::anyhow!(
+                "\nConfig Error:\nbump_files are not supported with packages, their Cargo.toml files are bumped instead"
+            )
+            .context("".red().to_string()));
+        }
+
+        for file in self.bump_files.iter().flatten() {
+            if file.kind == BumpFileKind::Regex && file.pattern.is_none() {
+                return Err(anyhow::anyhow!(
+                    "\nConfig Error:\nbump file {} has kind regex but no pattern",
+                    file.path,
+                )
+                .context("".red().to_string()));
+            }
+        }
+
         Ok(())
     }
 
-    /// Serialize the [`Default`] config and write it to `path`, creating or
-    /// truncating the file as needed. Panics if the file cannot be opened.
-    pub fn write_default(path: &str) {
-        let file = std::fs::OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(path)
-            .unwrap_or_else(|_| panic!("Error: couldn't open '{}'", path));
+    /// Returns the change of a non-breaking commit: the one of the first
+    /// matching `bump_rules`, else the one of `minor_trigger` and
+    /// `patch_trigger`.
+    pub fn change(
+        &self,
+        commit_type: &str,
+        scope: Option<&str>,
+        footer: Option<&str>,
+    ) -> SemVerChangeType {
+        let rule = self
+            .bump_rules
+            .iter()
+            .flatten()
+            .find(|x| x.matches(commit_type, scope, footer));
+        match rule {
+            Some(rule) => rule.change.clone(),
+            None if self.minor_trigger.iter().any(|x| x == commit_type) => SemVerChangeType::Minor,
+            None if self.patch_trigger.iter().any(|x| x == commit_type) => SemVerChangeType::Patch,
+            None => SemVerChangeType::None,
+        }
+    }
+
+    /// Returns the versioning scheme of the `package` tags, or of the
+    /// repository ones when `None`. Defaults to `semver`.
+    pub fn scheme(&self, package: Option<&str>) -> anyhow::Result<Scheme> {
+        let package = match package {
+            Some(name) => self.package(name)?.scheme.clone(),
+            None => None,
+        };
+        Ok(package.or(self.scheme.clone()).unwrap_or(Scheme::SemVer))
+    }
+
+    /// Returns the format of the `calver` scheme, `calver.format` or
+    /// [`DEFAULT_CALVER_FORMAT`].
+    pub fn calver_format(&self) -> anyhow::Result<CalVerFormat> {
+        self.calver
+            .as_ref()
+            .and_then(|x| x.format.as_deref())
+            .unwrap_or(DEFAULT_CALVER_FORMAT)
+            .parse::<CalVerFormat>()
+    }
+
+    /// Serialize the [`Default`] config to YAML.
+    pub fn default_yaml() -> anyhow::Result<String> {
+        Ok(serde_yaml::to_string(&Self::default())?)
+    }
+
+    /// Returns the entry of `packages` called `name`.
+    pub fn package(&self, name: &str) -> anyhow::Result<&Package> {
+        self.packages
+            .iter()
+            .flatten()
+            .find(|x| x.name == name)
+            .ok_or(anyhow::anyhow!(
+                "package {} not found, configured packages are {:?}",
+                name,
+                self.packages
+                    .iter()
+                    .flatten()
+                    .map(|x| &x.name)
+                    .collect::<Vec<&String>>()
+            ))
+    }
+
+    /// Returns the first entry of `branches` matching `branch`.
+    pub fn branch(&self, branch: &str) -> Option<&Branch> {
+        self.branches
+            .iter()
+            .flatten()
+            .find(|x| glob_match(&x.pattern, branch))
+    }
 
-        serde_yaml::to_writer(file, &Self::default()).unwrap();
+    /// Applies the release channel of the first entry of `branches` matching
+    /// `branch` to the `tag` settings. Does nothing when no entry matches.
+    pub fn apply_branch(&mut self, branch: &str) {
+        let Some(found) = self.branch(branch).cloned() else {
+            return;
+        };
+        let tag = self.tag.get_or_insert_with(Tag::default);
+        tag.pre_release = found.pre_release;
+        tag.filter = found.filter;
     }
 
     /// Returns `true` if `to_check` is listed as a valid commit type in `cc_types`.
@@ -126,6 +720,13 @@ impl Config {
     }
 }
 
+/// Returns `true` if `text` matches `pattern`, where `*` matches any
+/// sequence of characters.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let regex = format!("^{}$", regex::escape(pattern).replace(r"\*", ".*"));
+    Regex::new(&regex).is_ok_and(|x| x.is_match(text))
+}
+
 impl Default for Config {
     /// Sensible out-of-the-box defaults:
     /// - recognises `feat`, `fix`, `ci`, and `chore` commit types
@@ -142,33 +743,34 @@ impl Default for Config {
             minor_trigger: vec!["feat".to_owned()],
             patch_trigger: vec!["fix".to_owned()],
             check: Some(Check { name: None, diff: None }),
-            tag: Some(Tag {
-                merged: None,
-                no_merged: None,
-                ignore_prereleases: None,
-            }),
+            tag: Some(Tag::default()),
+            bump_files: None,
+            branches: None,
+            packages: None,
+            cascade: None,
+            scheme: None,
+            calver: None,
+            pre_1_0: None,
+            bump_rules: None,
+            rules: None,
+            scopes: None,
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Config;
+    use super::{Branch, BumpFile, BumpFileKind, Config, Package};
+    use crate::utils::errors::ConventionalCommitParseError;
+    use crate::utils::{ConventionalCommit, SemVerChangeType};
 
     /// Baseline config used across tests — mirrors the `Default` impl
     /// but with `check` and `tag` set to `None` for simplicity.
     fn base_config() -> Config {
         Config {
-            cc_types: vec![
-                "feat".to_owned(),
-                "fix".to_owned(),
-                "ci".to_owned(),
-                "chore".to_owned(),
-            ],
-            minor_trigger: vec!["feat".to_owned()],
-            patch_trigger: vec!["fix".to_owned()],
             check: None,
             tag: None,
+            ..Config::default()
         }
     }
 
@@ -191,4 +793,160 @@ mod tests {
         };
         assert!(config.config_check().is_err());
     }
+
+    /// The first branch entry matching the current branch sets the channel.
+    #[test]
+    fn branch_channel() {
+        let branch = |pattern: &str, pre_release: Option<&str>| Branch {
+            pattern: pattern.to_owned(),
+            pre_release: pre_release.map(str::to_owned),
+            filter: None,
+        };
+        let mut config = Config {
+            branches: Some(vec![
+                branch("main", None),
+                branch("develop", Some("beta")),
+                branch("release/*", Some("rc")),
+                branch("*", Some("alpha")),
+            ]),
+            ..base_config()
+        };
+        assert_eq!(config.branch("release/1.2").unwrap().pattern, "release/*");
+        assert_eq!(config.branch("feature/x").unwrap().pattern, "*");
+
+        config.apply_branch("develop");
+        assert_eq!(
+            config.tag.as_ref().unwrap().pre_release.as_deref(),
+            Some("beta")
+        );
+        config.apply_branch("main");
+        assert_eq!(config.tag.as_ref().unwrap().pre_release, None);
+    }
+
+    /// A package only owns the commits with one of its `scopes`, when set.
+    #[test]
+    fn package_scopes() {
+        let mut package = Package {
+            name: "cli".to_owned(),
+            path: "crates/cli".to_owned(),
+            tag_prefix: None,
+            scopes: None,
+            scheme: None,
+        };
+        assert!(package.has_scope(None));
+        package.scopes = Some(vec!["cli".to_owned()]);
+        assert!(package.has_scope(Some("cli")));
+        assert!(!package.has_scope(Some("core")));
+        assert!(!package.has_scope(None));
+
+        let mut config = Config {
+            packages: Some(vec![package]),
+            ..base_config()
+        };
+        assert!(config.config_check().is_ok());
+        config.bump_files = Some(vec![BumpFile {
+            path: "package.json".to_owned(),
+            kind: BumpFileKind::Npm,
+            pattern: None,
+        }]);
+        assert!(config.config_check().is_err());
+    }
+
+    /// The first matching bump rule sets the change, the triggers apply otherwise.
+    #[test]
+    fn bump_rules() {
+        let config = Config {
+            bump_rules: Some(
+                serde_yaml::from_str(
+                    "- {type: fix, scope: deps, change: none}\n- {footer: Security, change: minor}\n- {type: feat, scope: internal, change: patch}\n- {type: chore, change: patch}",
+                )
+                .unwrap(),
+            ),
+            ..base_config()
+        };
+        assert!(config.config_check().is_ok());
+        assert_eq!(
+            config.change("fix", Some("deps"), None),
+            SemVerChangeType::None
+        );
+        assert_eq!(
+            config.change("fix", Some("deps"), Some("Security: CVE-1")),
+            SemVerChangeType::None
+        );
+        assert_eq!(
+            config.change("fix", None, Some("Refs: #1\nSecurity #2")),
+            SemVerChangeType::Minor
+        );
+        assert_eq!(
+            config.change("fix", None, Some("Security-Fix: x")),
+            SemVerChangeType::Patch
+        );
+        assert_eq!(
+            config.change("feat", Some("internal"), None),
+            SemVerChangeType::Patch
+        );
+        assert_eq!(
+            config.change("feat", Some("cli"), None),
+            SemVerChangeType::Minor
+        );
+        assert_eq!(config.change("chore", None, None), SemVerChangeType::Patch);
+        assert_eq!(config.change("ci", None, None), SemVerChangeType::None);
+
+        // The footer token of a commit without a body paragraph
+        for message in [
+            "fix: bump openssl\n\nSecurity: CVE-1\n",
+            "fix: x\n\nbody\n\nSecurity #2",
+        ] {
+            let commit = ConventionalCommit::new(message, &config, "abc".to_owned()).unwrap();
+            assert_eq!(*commit.change(), SemVerChangeType::Minor);
+        }
+
+        let config = Config {
+            bump_rules: Some(serde_yaml::from_str("- {type: perf, change: patch}").unwrap()),
+            ..base_config()
+        };
+        assert!(config.config_check().is_err());
+    }
+
+    /// `scopes` validates the scope against the allowed ones and the type policy.
+    #[test]
+    fn scopes() {
+        let yaml = "{allowed: [cli, parser], patterns: ['deps(-dev)?'], types: {feat: required, ci: forbidden}}";
+        let config = Config {
+            scopes: Some(serde_yaml::from_str(yaml).unwrap()),
+            ..base_config()
+        };
+        assert!(config.config_check().is_ok());
+        let scopes = config.scopes.as_ref().unwrap();
+
+        assert!(scopes.check("feat", Some("cli")).is_ok());
+        assert!(scopes.check("fix", Some("deps-dev")).is_ok());
+        assert!(scopes.check("fix", None).is_ok());
+        assert_eq!(
+            scopes.check("fix", Some("auht")),
+            Err(ConventionalCommitParseError::InvalidScope {
+                expected: vec![
+                    "cli".to_owned(),
+                    "parser".to_owned(),
+                    "/deps(-dev)?/".to_owned()
+                ],
+                found: "auht".to_owned(),
+            })
+        );
+        assert!(scopes.check("fix", Some("mydeps")).is_err());
+        assert_eq!(
+            scopes.check("feat", None).unwrap_err().code(),
+            "missing-scope"
+        );
+        assert_eq!(
+            scopes.check("ci", Some("cli")).unwrap_err().code(),
+            "unexpected-scope"
+        );
+
+        let config = Config {
+            scopes: Some(serde_yaml::from_str("{types: {perf: required}}").unwrap()),
+            ..base_config()
+        };
+        assert!(config.config_check().is_err());
+    }
 }
@@ -2,16 +2,23 @@ use anyhow::Ok;
 use clap::Parser;
 use env_logger::Env;
 use log::{debug, warn};
-use ruvex::config::Config;
+use ruvex::command::{VersionOutput, VersionQuery};
+use ruvex::config::{Config, Tag};
 use ruvex::utils::cli::{RuvexArgs, RuvexCommand};
+use ruvex::utils::{git, plan};
 use std::path::Path;
 
-fn create_default_config_file(config_path: &Path) -> anyhow::Result<()> {
+fn create_default_config_file(config_path: &Path, dry_run: bool) -> anyhow::Result<()> {
     debug!("trying to create default path {:#?}", config_path);
     let dir_path = config_path.parent().unwrap();
-    std::fs::create_dir_all(dir_path)?;
-    Config::write_default(config_path.to_str().unwrap());
-    Ok(())
+    if dry_run {
+        if !dir_path.exists() {
+            plan::report(&format!("create directory {}", dir_path.display()));
+        }
+    } else {
+        std::fs::create_dir_all(dir_path)?;
+    }
+    plan::write_file(config_path, &Config::default_yaml()?, dry_run)
 }
 
 fn main() -> anyhow::Result<()> {
@@ -40,30 +47,131 @@ fn main() -> anyhow::Result<()> {
 
     // create default config file if asked
     if args.create_default {
-        create_default_config_file(&config_path)?;
+        create_default_config_file(config_path, args.dry_run)?;
     }
 
     //Init Config
-    let config = Config::new(
-        &args
-            .config_path
-            .unwrap_or(config_path.to_str().unwrap().to_owned()),
-        default_config_path.to_str().unwrap(),
-    )?;
+    let mut config = if args.create_default && args.dry_run && !config_path.exists() {
+        // the default config was only previewed, use it as is
+        Config::default()
+    } else {
+        Config::new(
+            &args
+                .config_path
+                .clone()
+                .unwrap_or(config_path.to_str().unwrap().to_owned()),
+            default_config_path.to_str().unwrap(),
+        )?
+    };
+    // Pick the release channel of the current branch
+    if config.branches.is_some() {
+        match git::current_branch()? {
+            Some(branch) => {
+                debug!("current branch is {}", branch);
+                config.apply_branch(&branch);
+            }
+            None => warn!("HEAD is detached, set RUVEX_BRANCH to apply the branches config"),
+        }
+    }
+
+    // CLI flags take precedence over the config file
+    if let Some(prefix) = args.tag_prefix {
+        config.tag.get_or_insert_with(Tag::default).prefix = Some(prefix);
+    }
+    if let Some(pre) = args.pre {
+        config.tag.get_or_insert_with(Tag::default).pre_release = Some(pre);
+    }
+    if let Some(build_meta) = args.build_meta {
+        config.tag.get_or_insert_with(Tag::default).build_meta = Some(build_meta);
+    }
+
     //Check Config
     config.config_check()?;
 
     debug!("config is {:#?}", config);
     match args.command {
-        Some(RuvexCommand::Check { name, format }) => {
-            ruvex::command::check(name, format, &config, true)?;
+        Some(RuvexCommand::Check {
+            name,
+            format,
+            output,
+            message_file,
+            stdin,
+        }) => {
+            let message = match message_file {
+                Some(ref path) => Some(std::fs::read_to_string(path)?),
+                None if stdin => Some(std::io::read_to_string(std::io::stdin())?),
+                None => None,
+            };
+            match message {
+                Some(ref message) => ruvex::command::check_message(message, &config)?,
+                None => {
+                    let package = match args.package {
+                        Some(ref package) => Some(config.package(package)?),
+                        None => None,
+                    };
+                    ruvex::command::check_output(name, package, format, output, &config)?;
+                }
+            }
         }
         Some(RuvexCommand::Tag {
             merged,
             no_merged,
+            ignore_prereleases,
+            create,
+            release_as,
+            name,
+        }) => {
+            let query = VersionQuery {
+                name,
+                merged,
+                no_merged,
+                ignore_prereleases,
+                package: args.package,
+                release_as,
+            };
+            ruvex::command::tag(query, create, args.dry_run, &config)?
+        }
+        Some(RuvexCommand::Changelog {
+            output,
+            ignore_prereleases,
+        }) => ruvex::command::changelog(output, ignore_prereleases, args.dry_run, &config)?,
+        Some(RuvexCommand::Bump {
             ignore_prereleases,
             name,
-        }) => ruvex::command::tag(name, merged, no_merged, ignore_prereleases, &config)?,
+        }) => {
+            let query = VersionQuery {
+                name,
+                ignore_prereleases,
+                package: args.package,
+                ..Default::default()
+            };
+            ruvex::command::bump(query, args.dry_run, &config)?
+        }
+        Some(RuvexCommand::Promote) => {
+            ruvex::command::promote(args.package, args.dry_run, &config)?
+        }
+        Some(RuvexCommand::Version {
+            dev,
+            current,
+            next: _,
+            bump,
+            json,
+            ignore_prereleases,
+        }) => {
+            let output = match (dev, current, bump, json) {
+                (true, _, _, _) => VersionOutput::Dev,
+                (_, true, _, _) => VersionOutput::Current,
+                (_, _, true, _) => VersionOutput::Bump,
+                (_, _, _, true) => VersionOutput::Json,
+                _ => VersionOutput::Next,
+            };
+            ruvex::command::version(output, args.package, ignore_prereleases, &config)?
+        }
+        Some(RuvexCommand::Hooks { action }) => ruvex::command::hooks(
+            action,
+            args.config_path.as_deref().map(Path::new),
+            args.dry_run,
+        )?,
         _ => {}
     }
     Ok(())
@@ -1,7 +1,18 @@
+pub mod build_meta;
+pub mod bump;
+pub mod calver;
 pub mod cc;
 pub mod cli;
+pub mod date;
 pub mod errors;
+pub mod lint;
+pub mod manifest;
+pub mod pep440;
+pub mod plan;
 pub mod semver;
+pub mod tag_pattern;
+pub mod version;
+pub mod workspace;
 pub use cc::{CCVec, ConventionalCommit};
 pub use semver::SemVerChangeType;
 pub mod git;
@@ -0,0 +1,114 @@
+use crate::utils::date::Date;
+use crate::utils::git;
+use anyhow::anyhow;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+/// Values available to a build metadata template.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BuildInfo {
+    /// Short SHA of HEAD, rendered by `{sha}`.
+    pub sha: String,
+    /// Current UTC date, rendered by `{date}` as `YYYYMMDD`.
+    pub date: Date,
+    /// Number of commits since the latest tag, rendered by `{count}`.
+    pub count: usize,
+}
+
+impl BuildInfo {
+    /// Collects the build information of HEAD, `count` being the number of
+    /// commits since the latest tag.
+    pub fn collect(count: usize) -> anyhow::Result<Self> {
+        let sha = String::from_utf8(git::rev_parse(&["--short", "HEAD"])?.stdout)?;
+        Ok(Self {
+            sha: sha.trim().to_owned(),
+            date: Date::today(),
+            count,
+        })
+    }
+}
+
+/// Renders a build metadata `template` such as `g{sha}.{env:BUILD_ID}`.
+///
+/// Supported placeholders are `{sha}`, `{date}`, `{count}` and `{env:NAME}`.
+/// Characters that are not allowed in SemVer build metadata are replaced by `-`.
+///
+/// # Errors
+/// - Returns [`Err`] if a referenced environment variable is not set.
+/// - Returns [`Err`] if the result contains an empty identifier (e.g. `a..b`).
+pub fn render(template: &str, info: &BuildInfo) -> anyhow::Result<String> {
+    lazy_static! {
+        static ref ENV: Regex = Regex::new(r"\{env:(?P<name>[A-Za-z_][A-Za-z0-9_]*)\}").unwrap();
+        static ref INVALID: Regex = Regex::new(r"[^0-9A-Za-z.-]").unwrap();
+    }
+
+    let mut missing: Vec<String> = Vec::new();
+    let rendered = ENV.replace_all(template, |caps: &Captures| {
+        std::env::var(&caps["name"]).unwrap_or_else(|_| {
+            missing.push(caps["name"].to_owned());
+            String::new()
+        })
+    });
+    if !missing.is_empty() {
+        return Err(anyhow!(
+            "build metadata {:?} uses unset environment variables {:?}",
+            template,
+            missing
+        ));
+    }
+
+    let rendered = rendered
+        .replace("{sha}", &info.sha)
+        .replace("{date}", &info.date.compact())
+        .replace("{count}", &info.count.to_string());
+    let rendered = INVALID.replace_all(&rendered, "-").into_owned();
+
+    if rendered.split('.').any(str::is_empty) {
+        return Err(anyhow!(
+            "build metadata {:?} rendered to {:?}, which has an empty identifier",
+            template,
+            rendered
+        ));
+    }
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render, BuildInfo};
+    use crate::utils::date::Date;
+
+    fn info() -> BuildInfo {
+        BuildInfo {
+            sha: "3f2a1c9".to_owned(),
+            date: Date {
+                year: 2026,
+                month: 1,
+                day: 5,
+            },
+            count: 7,
+        }
+    }
+
+    #[test]
+    fn placeholders() {
+        assert_eq!(render("g{sha}", &info()).unwrap(), "g3f2a1c9");
+        assert_eq!(render("{date}.{count}", &info()).unwrap(), "20260105.7");
+    }
+
+    #[test]
+    fn env() {
+        std::env::set_var("RUVEX_TEST_BUILD_ID", "build/42");
+        assert_eq!(
+            render("{env:RUVEX_TEST_BUILD_ID}.g{sha}", &info()).unwrap(),
+            "build-42.g3f2a1c9"
+        );
+        assert!(render("{env:RUVEX_TEST_UNSET_VARIABLE}", &info()).is_err());
+    }
+
+    #[test]
+    fn empty_identifier() {
+        assert!(render("", &info()).is_err());
+        assert!(render("a..{sha}", &info()).is_err());
+    }
+}
@@ -0,0 +1,267 @@
+use crate::config::{BumpFile, BumpFileKind};
+use crate::utils::version::VersionScheme;
+use anyhow::anyhow;
+use regex::Regex;
+use std::fmt::Display;
+use std::ops::Range;
+use toml_edit::{DocumentMut, Item, Value};
+
+/// Key paths where `pyproject.toml` may store the version, in lookup order.
+const PYPROJECT_PATHS: [&[&str]; 2] = [&["project", "version"], &["tool", "poetry", "version"]];
+
+/// Key paths where `Cargo.toml` may store the version, in lookup order.
+const CARGO_PATHS: [&[&str]; 2] = [
+    &["package", "version"],
+    &["workspace", "package", "version"],
+];
+
+/// Returns the string value stored at `path` in a TOML document.
+fn toml_value_mut<'a>(doc: &'a mut DocumentMut, path: &[&str]) -> Option<&'a mut Value> {
+    let mut item: &mut Item = doc.as_item_mut();
+    for key in path {
+        item = item.as_table_like_mut()?.get_mut(key)?;
+    }
+    item.as_value_mut().filter(|x| x.is_str())
+}
+
+/// Returns the key paths a TOML `kind` may store its version at.
+fn toml_paths(kind: &BumpFileKind) -> &'static [&'static [&'static str]] {
+    match kind {
+        BumpFileKind::Pyproject => &PYPROJECT_PATHS,
+        _ => &CARGO_PATHS,
+    }
+}
+
+/// Finds the version of a TOML file and hands it to `f`.
+fn with_toml_version<T>(
+    doc: &mut DocumentMut,
+    file: &BumpFile,
+    f: impl FnOnce(&mut Value) -> T,
+) -> anyhow::Result<T> {
+    for path in toml_paths(&file.kind) {
+        if toml_value_mut(doc, path).is_some() {
+            return Ok(f(toml_value_mut(doc, path).unwrap()));
+        }
+    }
+    Err(anyhow!(
+        "no version found in {}, expected one of {:?}",
+        file.path,
+        toml_paths(&file.kind)
+            .iter()
+            .map(|x| x.join("."))
+            .collect::<Vec<String>>()
+    ))
+}
+
+/// Returns the byte range of the version in the text based file kinds.
+fn locate(content: &str, file: &BumpFile) -> anyhow::Result<Range<usize>> {
+    match file.kind {
+        BumpFileKind::Npm => locate_npm(content, file),
+        BumpFileKind::Regex => {
+            let regex = Regex::new(file.pattern.as_deref().unwrap_or_default())?;
+            locate_regex(content, &regex, file)
+        }
+        BumpFileKind::Plain => {
+            let start = content.len() - content.trim_start().len();
+            Ok(start..content.trim_end().len())
+        }
+        BumpFileKind::Cargo | BumpFileKind::Pyproject => {
+            unreachable!("TOML files are edited through toml_edit")
+        }
+    }
+}
+
+/// Returns the byte range of the top-level `"version"` string of a
+/// `package.json`, the `"version"` keys of nested objects are skipped.
+fn locate_npm(content: &str, file: &BumpFile) -> anyhow::Result<Range<usize>> {
+    let json: serde_json::Value = serde_json::from_str(content)?;
+    let missing = || anyhow!("no top-level \"version\" found in {}", file.path);
+    if !json.get("version").is_some_and(|x| x.is_string()) {
+        return Err(missing());
+    }
+    // The content is valid JSON, tracking strings and nesting is enough to
+    // locate the raw text of the top-level key so formatting is kept.
+    let bytes = content.as_bytes();
+    let string_end = |start: usize| {
+        let mut i = start + 1;
+        while bytes[i] != b'"' {
+            i += if bytes[i] == b'\\' { 2 } else { 1 };
+        }
+        i
+    };
+    let mut depth = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => depth -= 1,
+            b'"' => {
+                let end = string_end(i);
+                let rest = content[end + 1..].trim_start();
+                if depth == 1 && &content[i + 1..end] == "version" && rest.starts_with(':') {
+                    let value = content.len() - rest[1..].trim_start().len();
+                    return Ok(value + 1..string_end(value));
+                }
+                i = end;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    Err(missing())
+}
+
+/// Returns the byte range of the `version` group of the first `regex` match.
+fn locate_regex(content: &str, regex: &Regex, file: &BumpFile) -> anyhow::Result<Range<usize>> {
+    regex
+        .captures(content)
+        .and_then(|x| x.name("version"))
+        .map(|x| x.range())
+        .ok_or(anyhow!(
+            "no version matching {} found in {}",
+            regex.as_str(),
+            file.path
+        ))
+}
+
+/// Parses the version found in `file`, with a readable error.
+fn parse_found<V: VersionScheme>(found: &str, file: &BumpFile) -> anyhow::Result<V> {
+    V::parse(found).ok_or(anyhow!(
+        "version {:?} of {} is not valid in the configured scheme",
+        found,
+        file.path
+    ))
+}
+
+/// Reads the version currently stored in `content`, the text of `file`, in
+/// the versioning scheme `V`.
+pub fn read_version<V: VersionScheme>(content: &str, file: &BumpFile) -> anyhow::Result<V> {
+    match file.kind {
+        BumpFileKind::Cargo | BumpFileKind::Pyproject => {
+            let mut doc = content.parse::<DocumentMut>()?;
+            let found = with_toml_version(&mut doc, file, |x| {
+                x.as_str().unwrap_or_default().to_owned()
+            })?;
+            parse_found(&found, file)
+        }
+        _ => parse_found(&content[locate(content, file)?], file),
+    }
+}
+
+/// Returns `content`, the text of `file`, with its version replaced by
+/// `version`. Everything else, including formatting and comments, is kept.
+pub fn write_version(
+    content: &str,
+    file: &BumpFile,
+    version: &impl Display,
+) -> anyhow::Result<String> {
+    match file.kind {
+        BumpFileKind::Cargo | BumpFileKind::Pyproject => {
+            let mut doc = content.parse::<DocumentMut>()?;
+            with_toml_version(&mut doc, file, |x| {
+                let decor = x.decor().clone();
+                *x = Value::from(version.to_string());
+                *x.decor_mut() = decor;
+            })?;
+            Ok(doc.to_string())
+        }
+        _ => {
+            let range = locate(content, file)?;
+            Ok(format!(
+                "{}{}{}",
+                &content[..range.start],
+                version,
+                &content[range.end..]
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_version, write_version};
+    use crate::config::{BumpFile, BumpFileKind};
+    use crate::utils::semver::SemVer;
+
+    fn bump_file(kind: BumpFileKind, pattern: Option<&str>) -> BumpFile {
+        BumpFile {
+            path: "test".to_owned(),
+            kind,
+            pattern: pattern.map(str::to_owned),
+        }
+    }
+
+    fn roundtrip(content: &str, file: &BumpFile, expected: &str) {
+        let next = "1.3.0".parse::<SemVer>().unwrap();
+        assert_eq!(
+            read_version::<SemVer>(content, file).unwrap(),
+            "1.2.0".parse::<SemVer>().unwrap()
+        );
+        assert_eq!(write_version(content, file, &next).unwrap(), expected);
+    }
+
+    #[test]
+    fn cargo() {
+        roundtrip(
+            "[package]\nname = \"x\"\nversion = \"1.2.0\" # keep me\n\n[dependencies]\nfoo = { version = \"0.1.0\" }\n",
+            &bump_file(BumpFileKind::Cargo, None),
+            "[package]\nname = \"x\"\nversion = \"1.3.0\" # keep me\n\n[dependencies]\nfoo = { version = \"0.1.0\" }\n",
+        );
+    }
+
+    #[test]
+    fn cargo_workspace() {
+        roundtrip(
+            "[workspace]\nmembers = [\"core\"]\n\n[workspace.package]\nversion = \"1.2.0\"\n",
+            &bump_file(BumpFileKind::Cargo, None),
+            "[workspace]\nmembers = [\"core\"]\n\n[workspace.package]\nversion = \"1.3.0\"\n",
+        );
+    }
+
+    #[test]
+    fn pyproject() {
+        roundtrip(
+            "[tool.poetry]\nname = \"x\"\nversion   = \"1.2.0\"\n",
+            &bump_file(BumpFileKind::Pyproject, None),
+            "[tool.poetry]\nname = \"x\"\nversion   = \"1.3.0\"\n",
+        );
+    }
+
+    #[test]
+    fn npm() {
+        roundtrip(
+            "{\n  \"name\": \"x\",\n  \"dependencies\": { \"version\": \"0.1.0\" },\n  \"version\":  \"1.2.0\"\n}\n",
+            &bump_file(BumpFileKind::Npm, None),
+            "{\n  \"name\": \"x\",\n  \"dependencies\": { \"version\": \"0.1.0\" },\n  \"version\":  \"1.3.0\"\n}\n",
+        );
+        // A nested "version" with the same value comes first
+        roundtrip(
+            "{\"engines\": {\"version\": \"1.2.0\"}, \"x\": \"{\\\"\", \"version\": \"1.2.0\"}",
+            &bump_file(BumpFileKind::Npm, None),
+            "{\"engines\": {\"version\": \"1.2.0\"}, \"x\": \"{\\\"\", \"version\": \"1.3.0\"}",
+        );
+        let file = bump_file(BumpFileKind::Npm, None);
+        assert!(read_version::<SemVer>("{\"engines\": {\"version\": \"1.2.0\"}}", &file).is_err());
+    }
+
+    #[test]
+    fn plain_and_regex() {
+        roundtrip("1.2.0\n", &bump_file(BumpFileKind::Plain, None), "1.3.0\n");
+        roundtrip(
+            "# v\n__version__ = '1.2.0'\n",
+            &bump_file(
+                BumpFileKind::Regex,
+                Some("__version__ = '(?P<version>[^']+)'"),
+            ),
+            "# v\n__version__ = '1.3.0'\n",
+        );
+    }
+
+    #[test]
+    fn missing_version() {
+        let file = bump_file(BumpFileKind::Cargo, None);
+        assert!(read_version::<SemVer>("[package]\nname = \"x\"\n", &file).is_err());
+        let file = bump_file(BumpFileKind::Regex, Some("VERSION=(?P<version>.+)"));
+        assert!(read_version::<SemVer>("nothing here", &file).is_err());
+    }
+}
@@ -0,0 +1,240 @@
+use crate::utils::date::Date;
+use anyhow::anyhow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// Format used when `calver.format` is not configured.
+pub const DEFAULT_CALVER_FORMAT: &str = "YYYY.0M.MICRO";
+
+/// A dot separated segment of a CalVer format, see <https://calver.org>.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Segment {
+    /// `YYYY`, full year, e.g. 2026.
+    Year,
+    /// `YY`, year since 2000, e.g. 6 or 26.
+    ShortYear,
+    /// `0Y`, zero-padded year since 2000, e.g. 06 or 26.
+    ZeroYear,
+    /// `MM`, month, e.g. 1 or 10.
+    Month,
+    /// `0M`, zero-padded month, e.g. 01 or 10.
+    ZeroMonth,
+    /// `DD`, day of the month, e.g. 7 or 17.
+    Day,
+    /// `0D`, zero-padded day of the month, e.g. 07 or 17.
+    ZeroDay,
+    /// `MICRO`, release counter within the same date, starting at 0.
+    Micro,
+}
+
+impl Segment {
+    /// Returns the value of the segment for `date`, `None` for [`Segment::Micro`].
+    fn value(&self, date: &Date) -> Option<u64> {
+        let value = match self {
+            Self::Year => date.year,
+            Self::ShortYear | Self::ZeroYear => date.year - 2000,
+            Self::Month | Self::ZeroMonth => date.month,
+            Self::Day | Self::ZeroDay => date.day,
+            Self::Micro => return None,
+        };
+        Some(u64::from(value))
+    }
+
+    /// Renders `value` with the padding of the segment.
+    fn render(&self, value: u64) -> String {
+        match self {
+            Self::ZeroYear | Self::ZeroMonth | Self::ZeroDay => format!("{:02}", value),
+            _ => value.to_string(),
+        }
+    }
+}
+
+impl FromStr for Segment {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "YYYY" => Ok(Self::Year),
+            "YY" => Ok(Self::ShortYear),
+            "0Y" => Ok(Self::ZeroYear),
+            "MM" => Ok(Self::Month),
+            "0M" => Ok(Self::ZeroMonth),
+            "DD" => Ok(Self::Day),
+            "0D" => Ok(Self::ZeroDay),
+            "MICRO" => Ok(Self::Micro),
+            _ => Err(anyhow!(
+                "unknown CalVer segment {:?}, expected one of YYYY, YY, 0Y, MM, 0M, DD, 0D, MICRO",
+                s
+            )),
+        }
+    }
+}
+
+/// A dot separated CalVer format, e.g. `YYYY.0M.MICRO`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CalVerFormat {
+    segments: Vec<Segment>,
+}
+
+impl FromStr for CalVerFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let segments = s
+            .split('.')
+            .map(str::parse::<Segment>)
+            .collect::<anyhow::Result<Vec<Segment>>>()?;
+        let micros = segments.iter().filter(|x| **x == Segment::Micro).count();
+        if micros > 1
+            || segments
+                .last()
+                .is_some_and(|x| micros == 1 && *x != Segment::Micro)
+        {
+            return Err(anyhow!("MICRO can only be the last segment of {:?}", s));
+        }
+        if micros == segments.len() {
+            return Err(anyhow!("{:?} holds no date segment", s));
+        }
+        Ok(Self { segments })
+    }
+}
+
+impl CalVerFormat {
+    /// Parses `text` as a version of this format, `None` when it does not
+    /// match it exactly, padding included.
+    pub fn parse(&self, text: &str) -> Option<CalVer> {
+        let values = text
+            .split('.')
+            .map(|x| x.parse::<u64>().ok())
+            .collect::<Option<Vec<u64>>>()?;
+        if values.len() != self.segments.len() {
+            return None;
+        }
+        let version = CalVer {
+            format: self.clone(),
+            values,
+        };
+        (version.to_string() == text).then_some(version)
+    }
+
+    /// Returns the version released on `today` after `latest`: the date
+    /// segments are set from `today` and `MICRO` counts the releases of the
+    /// same date.
+    ///
+    /// # Errors
+    /// - Returns [`Err`] if the next version would not be greater than
+    ///   `latest`, e.g. when releasing twice the same day without `MICRO`.
+    pub fn next(&self, latest: Option<&CalVer>, today: &Date) -> anyhow::Result<CalVer> {
+        let mut values: Vec<u64> = self
+            .segments
+            .iter()
+            .map(|x| x.value(today).unwrap_or_default())
+            .collect();
+
+        let micro = self.segments.last() == Some(&Segment::Micro);
+        if let Some(latest) = latest {
+            let date_len = self.segments.len() - usize::from(micro);
+            if micro && latest.values[..date_len] == values[..date_len] {
+                values[date_len] = latest.values[date_len] + 1;
+            }
+        }
+
+        let next = CalVer {
+            format: self.clone(),
+            values,
+        };
+        match latest {
+            Some(latest) if next <= *latest => Err(anyhow!(
+                "next CalVer {} is not greater than the latest one {}{}",
+                next,
+                latest,
+                if micro {
+                    ""
+                } else {
+                    ", add MICRO to the format to release several times a period"
+                }
+            )),
+            _ => Ok(next),
+        }
+    }
+}
+
+/// A calendar version, rendered and compared according to its format.
+#[derive(Debug, Clone)]
+pub struct CalVer {
+    format: CalVerFormat,
+    values: Vec<u64>,
+}
+
+impl PartialEq for CalVer {
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
+impl Eq for CalVer {}
+
+impl PartialOrd for CalVer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CalVer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.values.cmp(&other.values)
+    }
+}
+
+impl fmt::Display for CalVer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let parts: Vec<String> = self
+            .format
+            .segments
+            .iter()
+            .zip(&self.values)
+            .map(|(segment, value)| segment.render(*value))
+            .collect();
+        write!(f, "{}", parts.join("."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CalVerFormat;
+    use crate::utils::date::Date;
+
+    #[test]
+    fn parse() {
+        let format = "YYYY.0M.MICRO".parse::<CalVerFormat>().unwrap();
+        assert_eq!(format.parse("2026.01.3").unwrap().to_string(), "2026.01.3");
+        assert!(format.parse("2026.1.3").is_none());
+        assert!(format.parse("1.2.3-rc.1").is_none());
+        assert!("YYYY.MICRO.0M".parse::<CalVerFormat>().is_err());
+        assert!("YYYY.WW".parse::<CalVerFormat>().is_err());
+    }
+
+    #[test]
+    fn next() {
+        let today = Date {
+            year: 2026,
+            month: 10,
+            day: 17,
+        };
+        let format = "YYYY.0M.MICRO".parse::<CalVerFormat>().unwrap();
+        let next = |latest: &str| {
+            format
+                .next(format.parse(latest).as_ref(), &today)
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(next("2026.09.4"), "2026.10.0");
+        assert_eq!(next("2026.10.1"), "2026.10.2");
+        assert_eq!(format.next(None, &today).unwrap().to_string(), "2026.10.0");
+
+        let format = "0Y.0M.0D".parse::<CalVerFormat>().unwrap();
+        let latest = format.parse("26.10.17").unwrap();
+        assert!(format.next(Some(&latest), &today).is_err());
+    }
+}
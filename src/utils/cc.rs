@@ -2,6 +2,7 @@ use crate::config::Config;
 use crate::utils::errors::ConventionalCommitParseError;
 use crate::utils::SemVerChangeType;
 use core::fmt;
+use lazy_static::lazy_static;
 use regex::Regex;
 
 pub trait CCVec {
@@ -13,13 +14,41 @@ pub trait CCVec {
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct ConventionalCommit {
-    commit_type: String,       // feat
-    short_sha: String,         //
-    scope: Option<String>,     // ()
-    change: SemVerChangeType,  // !
-    short_description: String, // : to \n both excluded
-    body: Option<String>,      // remainder of commit message
-    footer: Option<String>,    // optional footer
+    pub(crate) commit_type: String,       // feat
+    pub(crate) short_sha: String,         //
+    pub(crate) scope: Option<String>,     // ()
+    pub(crate) change: SemVerChangeType,  // !
+    pub(crate) short_description: String, // : to \n both excluded
+    pub(crate) body: Option<String>,      // remainder of commit message
+    pub(crate) footer: Option<String>,    // optional footer
+}
+
+/// Returns the value of the `token` trailer of `footer`, written either
+/// `Token: value` or `Token #value`. Tokens are matched case-insensitively.
+pub(crate) fn footer_value<'a>(footer: &'a str, token: &str) -> Option<&'a str> {
+    footer.lines().find_map(|line| {
+        let found = line.get(..token.len())?;
+        if !found.eq_ignore_ascii_case(token) {
+            return None;
+        }
+        let rest = &line[token.len()..];
+        rest.strip_prefix(": ")
+            .or(rest.strip_prefix(" #"))
+            .map(str::trim)
+    })
+}
+
+/// Returns `true` if every line of `paragraph` is a git trailer such as
+/// `Refs: #12`, `Closes #3` or `BREAKING CHANGE: x`, or the indented
+/// continuation of one.
+fn is_trailers(paragraph: &str) -> bool {
+    lazy_static! {
+        static ref TRAILER: Regex =
+            Regex::new(r"^(BREAKING CHANGE|[A-Za-z][\w-]*)(: | #)").unwrap();
+    }
+    let mut lines = paragraph.lines().filter(|x| !x.trim().is_empty());
+    lines.next().is_some_and(|x| TRAILER.is_match(x))
+        && lines.all(|x| TRAILER.is_match(x) || x.starts_with(char::is_whitespace))
 }
 
 // check for type in types list
@@ -53,11 +82,64 @@ impl CCVec for Vec<ConventionalCommit> {
         self.iter().any(|x| x.is_major())
     }
     fn max_change(&self) -> SemVerChangeType {
-        self.iter().map(|x| x.change.clone()).max().unwrap()
+        self.iter()
+            .map(|x| x.change.clone())
+            .max()
+            .unwrap_or(SemVerChangeType::None)
     }
 }
 
 impl ConventionalCommit {
+    /// Returns the type of the commit, e.g. `feat`.
+    pub fn commit_type(&self) -> &str {
+        &self.commit_type
+    }
+
+    /// Returns the short SHA of the commit.
+    pub fn short_sha(&self) -> &str {
+        &self.short_sha
+    }
+
+    /// Returns the scope of the commit, e.g. `cli` for `feat(cli): new flag`.
+    pub fn scope(&self) -> Option<&str> {
+        self.scope.as_deref()
+    }
+
+    /// Returns the change the commit calls for.
+    pub fn change(&self) -> &SemVerChangeType {
+        &self.change
+    }
+
+    /// Returns the description following the type and scope.
+    pub fn short_description(&self) -> &str {
+        &self.short_description
+    }
+
+    /// Returns the body of the commit, without its footer.
+    pub fn body(&self) -> Option<&str> {
+        self.body.as_deref()
+    }
+
+    /// Returns the footer of the commit, e.g. `Refs: #12`.
+    pub fn footer(&self) -> Option<&str> {
+        self.footer.as_deref()
+    }
+
+    /// Returns the commit header without its body, e.g. `feat(cli)!: new flag`.
+    pub fn header(&self) -> String {
+        let bc = if self.change == SemVerChangeType::Major {
+            "!"
+        } else {
+            ""
+        };
+        match self.scope {
+            Some(ref scope) => format!(
+                "{}({}){}: {}",
+                self.commit_type, scope, bc, self.short_description
+            ),
+            None => format!("{}{}: {}", self.commit_type, bc, self.short_description),
+        }
+    }
     fn is_patch(&self) -> bool {
         self.change == SemVerChangeType::Minor
     }
@@ -76,7 +158,6 @@ impl ConventionalCommit {
         // check for :
         let mut cc_type: String;
         let mut scope: Option<String>;
-        let mut change: SemVerChangeType;
         let regex: Regex = Regex::new(r"\(.+\)").unwrap();
         let short_description: String;
         let mut body: Option<String>;
@@ -113,15 +194,9 @@ impl ConventionalCommit {
         }
 
         //check for ! and pop it
-        if cc_type.ends_with('!') {
-            change = SemVerChangeType::Major;
+        let mut breaking = cc_type.ends_with('!');
+        if breaking {
             cc_type = cc_type.replace('!', "");
-        } else if config.minor_trigger.contains(&cc_type) {
-            change = SemVerChangeType::Minor;
-        } else if config.patch_trigger.contains(&cc_type) {
-            change = SemVerChangeType::Patch;
-        } else {
-            change = SemVerChangeType::None;
         }
 
         // check if commit type is valid
@@ -132,6 +207,11 @@ impl ConventionalCommit {
             });
         }
 
+        // check the scope against the configured scopes
+        if let Some(ref scopes) = config.scopes {
+            scopes.check(&cc_type, scope.as_deref())?;
+        }
+
         (short_description, body) = match msg.split_once('\n') {
             Some(splitted) => {
                 let (short, long) = splitted;
@@ -144,27 +224,38 @@ impl ConventionalCommit {
             None => (msg.to_owned(), None),
         };
 
-        // check for footer
+        // check for footer: it starts at the first paragraph made of trailers,
+        // e.g. `Release-As: 2.0.0` right after the header, and runs to the end
+        // of the message. Without trailers it is the last paragraph.
         if let Some(ref text) = body {
-            (body, footer) = match text.split_once("\n\n") {
-                Some(splitted) => {
-                    let (short, long) = splitted;
+            let trailers = std::iter::once(0)
+                .chain(text.match_indices("\n\n").map(|(i, _)| i + 2))
+                .find(|&i| is_trailers(text[i..].split("\n\n").next().unwrap_or_default()));
+            (body, footer) = match (trailers, text.rsplit_once("\n\n")) {
+                (Some(0), _) => (None, Some(text.trim_start().to_owned())),
+                (Some(i), _) => (Some(text[..i - 2].to_owned()), Some(text[i..].to_owned())),
+                (None, Some((short, long))) => {
                     if long.is_empty() {
                         (Some(short.to_owned()), None)
                     } else {
                         (Some(short.to_owned()), Some(long.to_owned()))
                     }
                 }
-                None => (body, None),
+                (None, None) => (body, None),
             };
         }
 
         // check for BREAKING-CHANGE in footer
         if let Some(ref ft) = footer {
             if ft.contains("BREAKING-CHANGE: ") || ft.contains("BREAKING CHANGE: ") {
-                change = SemVerChangeType::Major;
+                breaking = true;
             }
         }
+
+        let change = match breaking {
+            true => SemVerChangeType::Major,
+            false => config.change(&cc_type, scope.as_deref(), footer.as_deref()),
+        };
         Ok(ConventionalCommit {
             commit_type: cc_type,
             short_sha,
@@ -190,8 +281,7 @@ mod tests {
                 cc_types,
                 check: None,
                 tag: None,
-                minor_trigger: vec!["feat".to_owned()],
-                patch_trigger: vec!["fix".to_owned()],
+                ..Config::default()
             },
             sha.to_owned(),
         )
@@ -210,8 +300,7 @@ mod tests {
                 cc_types,
                 check: None,
                 tag: None,
-                minor_trigger: vec!["feat".to_owned()],
-                patch_trigger: vec!["fix".to_owned()],
+                ..Config::default()
             },
             sha.to_owned(),
         );
@@ -444,4 +533,83 @@ mod tests {
         assert!(!vector.is_patch());
         assert!(vector.max_change() == SemVerChangeType::Minor);
     }
+    #[test]
+    fn accessors() {
+        let cc = ConventionalCommit::new(
+            "feat(cli)!: new flag\n\nbody\n\nRefs: #12",
+            &Config::default(),
+            "ababa".to_owned(),
+        )
+        .unwrap();
+        assert_eq!(cc.commit_type(), "feat");
+        assert_eq!(cc.short_sha(), "ababa");
+        assert_eq!(cc.scope(), Some("cli"));
+        assert_eq!(*cc.change(), SemVerChangeType::Major);
+        assert_eq!(cc.short_description(), "new flag");
+        assert_eq!(cc.body(), Some("\nbody"));
+        assert_eq!(cc.footer(), Some("Refs: #12"));
+        assert_eq!(cc.header(), "feat(cli)!: new flag");
+    }
+    #[test]
+    fn footer_without_body() {
+        let cc = ConventionalCommit::new(
+            "chore: release 2.0.0\n\nRelease-As: 2.0.0\n",
+            &Config::default(),
+            "ababa".to_owned(),
+        )
+        .unwrap();
+        assert_eq!(cc.body(), None);
+        assert_eq!(cc.footer(), Some("Release-As: 2.0.0\n"));
+
+        let cc = ConventionalCommit::new(
+            "fix: drop flag\n\nBREAKING CHANGE: the flag is gone",
+            &Config::default(),
+            "ababa".to_owned(),
+        )
+        .unwrap();
+        assert_eq!(*cc.change(), SemVerChangeType::Major);
+
+        let cc = ConventionalCommit::new(
+            "fix: typo\n\nnot: a trailer paragraph\nat all",
+            &Config::default(),
+            "ababa".to_owned(),
+        )
+        .unwrap();
+        assert_eq!(cc.footer(), None);
+
+        let cc = ConventionalCommit::new(
+            "fix: typo\n\nfirst\n\nsecond\n\nRefs: #12",
+            &Config::default(),
+            "ababa".to_owned(),
+        )
+        .unwrap();
+        assert_eq!(cc.body(), Some("\nfirst\n\nsecond"));
+        assert_eq!(cc.footer(), Some("Refs: #12"));
+    }
+    /// Paragraphs after the trailers stay in the footer.
+    #[test]
+    fn footer_followed_by_text() {
+        let cc = ConventionalCommit::new(
+            "fix: x\n\nBREAKING CHANGE: y\n\nmore text",
+            &Config::default(),
+            "ababa".to_owned(),
+        )
+        .unwrap();
+        assert_eq!(cc.body(), None);
+        assert_eq!(cc.footer(), Some("BREAKING CHANGE: y\n\nmore text"));
+        assert_eq!(*cc.change(), SemVerChangeType::Major);
+
+        let cc = ConventionalCommit::new(
+            "feat: x\n\nbody\n\nBREAKING CHANGE: y\n\nSee the migration guide.",
+            &Config::default(),
+            "ababa".to_owned(),
+        )
+        .unwrap();
+        assert_eq!(cc.body(), Some("\nbody"));
+        assert_eq!(
+            cc.footer(),
+            Some("BREAKING CHANGE: y\n\nSee the migration guide.")
+        );
+        assert_eq!(*cc.change(), SemVerChangeType::Major);
+    }
 }
@@ -1,3 +1,4 @@
+use crate::command::{CheckOutput, HooksAction};
 use clap::{Parser, Subcommand};
 
 #[derive(Parser, Debug)]
@@ -29,8 +30,25 @@ pub struct RuvexArgs {
     /// ignore prerelease tags when computing current version
     #[arg(short, long)]
     pub ignore_prereleases: bool,
+    /// print what would be created, tagged or written without changing anything
     #[arg(short, long)]
     pub dry_run: bool,
+
+    /// tag name prefix or pattern, e.g. "v" or "{package}@{version}" (overrides tag.prefix)
+    #[arg(long)]
+    pub tag_prefix: Option<String>,
+
+    /// pre-release channel of the next version, e.g. "rc" gives 1.3.0-rc.1 (overrides tag.pre_release)
+    #[arg(long)]
+    pub pre: Option<String>,
+
+    /// build metadata template, e.g. "g{sha}" gives 1.3.0+g3f2a1c9 (overrides tag.build_meta)
+    #[arg(long)]
+    pub build_meta: Option<String>,
+
+    /// name of the packages entry to check, tag, version, bump or promote, every package is considered when missing
+    #[arg(long)]
+    pub package: Option<String>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -45,6 +63,19 @@ pub enum RuvexCommand {
         ///format error table message by returning at nth char (default 40)
         #[arg(short, long)]
         format: Option<usize>,
+
+        ///report format: table, json, junit or sarif
+        #[arg(short, long, value_enum, default_value_t)]
+        output: CheckOutput,
+
+        ///check the commit message in this file instead of the history,
+        ///e.g. from a commit-msg hook
+        #[arg(long, conflicts_with_all(["name", "stdin"]))]
+        message_file: Option<String>,
+
+        ///check the commit message read from stdin instead of the history
+        #[arg(long, conflicts_with("name"))]
+        stdin: bool,
     },
     ///find next tag based on git history with semver
     Tag {
@@ -57,7 +88,64 @@ pub enum RuvexCommand {
         #[arg(long)]
         ignore_prereleases: bool,
 
+        ///create an annotated git tag for the next version on HEAD
+        #[arg(long)]
+        create: bool,
+
+        ///release this version instead of the computed one, e.g. 1.0.0
+        #[arg(long)]
+        release_as: Option<String>,
+
         #[arg(num_args(0..))]
         name: Option<Vec<String>>,
     },
+    ///generate a markdown changelog from the CC compliant git history
+    Changelog {
+        ///write the changelog to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<String>,
+
+        #[arg(long)]
+        ignore_prereleases: bool,
+    },
+    ///write the next version into the files listed in bump_files
+    Bump {
+        #[arg(long)]
+        ignore_prereleases: bool,
+
+        #[arg(num_args(0..))]
+        name: Option<Vec<String>>,
+    },
+    ///tag the commit of the latest pre-release with its stable version
+    Promote,
+    ///print the next version, and nothing else, to stdout
+    Version {
+        ///print a development version such as 1.4.0-dev.12+gabc1234
+        #[arg(long, group = "output")]
+        dev: bool,
+
+        ///print the latest tagged version
+        #[arg(long, group = "output")]
+        current: bool,
+
+        ///print the next version (default)
+        #[arg(long, group = "output")]
+        next: bool,
+
+        ///print the bump of the next release: none, patch, minor or major
+        #[arg(long, group = "output")]
+        bump: bool,
+
+        ///print the current, next, bump and dev values as JSON
+        #[arg(long, group = "output")]
+        json: bool,
+
+        #[arg(long)]
+        ignore_prereleases: bool,
+    },
+    ///install, remove or inspect the git hooks running ruvex checks
+    Hooks {
+        #[command(subcommand)]
+        action: HooksAction,
+    },
 }
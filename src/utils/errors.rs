@@ -1,6 +1,6 @@
 use thiserror::Error;
 
-#[derive(Error, Debug, PartialEq)]
+#[derive(Error, Debug, PartialEq, Clone)]
 pub enum ConventionalCommitParseError {
     #[error("Missing \":\"")]
     MissingColumn,
@@ -16,4 +16,34 @@ pub enum ConventionalCommitParseError {
         expected: Vec<String>,
         found: String,
     },
+
+    #[error("invalid scope\n(expected {expected:?},\nfound {found:?})")]
+    InvalidScope {
+        expected: Vec<String>,
+        found: String,
+    },
+
+    #[error("missing scope, required by type {commit_type:?}\n(expected {expected:?})")]
+    MissingScope {
+        commit_type: String,
+        expected: Vec<String>,
+    },
+
+    #[error("unexpected scope {found:?}, forbidden by type {commit_type:?}")]
+    UnexpectedScope { commit_type: String, found: String },
+}
+
+impl ConventionalCommitParseError {
+    /// Returns a stable identifier of the error kind, e.g. `invalid-type`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::MissingColumn => "missing-colon",
+            Self::EmptyScope => "empty-scope",
+            Self::NoSpaceAfterColumn => "no-space-after-colon",
+            Self::InvalidType { .. } => "invalid-type",
+            Self::InvalidScope { .. } => "invalid-scope",
+            Self::MissingScope { .. } => "missing-scope",
+            Self::UnexpectedScope { .. } => "unexpected-scope",
+        }
+    }
 }
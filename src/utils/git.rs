@@ -30,3 +30,41 @@ pub fn log(args: &[&str]) -> Result<Output> {
 pub fn tag(args: &[&str]) -> Result<Output> {
     generic_git_cmd(args, "tag")
 }
+
+pub fn rev_parse(args: &[&str]) -> Result<Output> {
+    generic_git_cmd(args, "rev-parse")
+}
+
+pub fn branch(args: &[&str]) -> Result<Output> {
+    generic_git_cmd(args, "branch")
+}
+
+pub fn config(args: &[&str]) -> Result<Output> {
+    generic_git_cmd(args, "config")
+}
+
+/// Returns the value of the git config `key`, `None` when it is not set.
+pub fn config_value(key: &str) -> Result<Option<String>> {
+    let value = String::from_utf8(config(&["--get", key])?.stdout)?;
+    let value = value.trim_end_matches('\n');
+    Ok((!value.is_empty()).then(|| value.to_owned()))
+}
+
+/// Returns the name of the checked out branch.
+///
+/// The `RUVEX_BRANCH` environment variable takes precedence, so detached
+/// HEAD checkouts (common in CI) can still be mapped to a branch.
+/// Returns `None` when HEAD is detached and `RUVEX_BRANCH` is not set.
+pub fn current_branch() -> Result<Option<String>> {
+    if let Ok(branch) = std::env::var("RUVEX_BRANCH") {
+        return Ok(Some(branch));
+    }
+    let name = String::from_utf8(branch(&["--show-current"])?.stdout)?;
+    let name = name.trim();
+    Ok((!name.is_empty()).then(|| name.to_owned()))
+}
+
+/// Creates an annotated tag `name` on the `target` object with the given `message`.
+pub fn annotated_tag(name: &str, message: &str, target: &str) -> Result<Output> {
+    tag(&["-a", name, "-m", message, target])
+}
@@ -0,0 +1,204 @@
+use crate::config::{Case, Rule, Rules, Severity};
+use crate::utils::ConventionalCommit;
+use std::fmt;
+
+/// Maximum header length when `header_max_length` has no value.
+const DEFAULT_HEADER_MAX_LENGTH: usize = 72;
+
+/// Maximum body line length when `body_max_line_length` has no value.
+const DEFAULT_BODY_MAX_LINE_LENGTH: usize = 100;
+
+/// Characters the description must not end with when `subject_full_stop`
+/// has no value.
+const DEFAULT_FULL_STOP: &str = ".";
+
+/// A lint rule broken by a commit message.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Violation {
+    /// Identifier of the rule, e.g. `header-max-length`.
+    pub rule: &'static str,
+    /// Severity configured for the rule, never [`Severity::Off`].
+    pub severity: Severity,
+    /// What is wrong with the message.
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}: {}", self.severity, self.rule, self.message)
+    }
+}
+
+/// Returns the severity of `rule` and its value, the `default` one when
+/// unset, or `None` when the rule is off.
+fn active<'a, T>(rule: &'a Option<Rule<T>>, default: &'a T) -> Option<(Severity, &'a T)> {
+    let rule = rule.as_ref()?;
+    (rule.severity != Severity::Off)
+        .then(|| (rule.severity, rule.value.as_ref().unwrap_or(default)))
+}
+
+/// Checks `message` against the `rules`, returning every violation found.
+///
+/// The rules on the header and body lines apply to the raw `message`, the
+/// ones on the type and description need its parsed `commit` and are
+/// skipped when it is `None`, i.e. when the message is not a Conventional
+/// Commit.
+///
+/// # Arguments
+/// - `message` – Raw commit message.
+///
+/// - `commit`  – The parsed `message`, if it is a Conventional Commit.
+///
+/// - `rules`   – Lint rules of the project configuration.
+pub fn lint(message: &str, commit: Option<&ConventionalCommit>, rules: &Rules) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let mut report = |rule: &'static str, severity: Severity, message: String| {
+        violations.push(Violation {
+            rule,
+            severity,
+            message,
+        })
+    };
+    let mut lines = message.lines();
+    let header = lines.next().unwrap_or_default();
+
+    if let Some((severity, max)) = active(&rules.header_max_length, &DEFAULT_HEADER_MAX_LENGTH) {
+        let length = header.chars().count();
+        if length > *max {
+            report(
+                "header-max-length",
+                severity,
+                format!(
+                    "header is {} characters long, the maximum is {}",
+                    length, max
+                ),
+            );
+        }
+    }
+
+    let body: Vec<&str> = lines.collect();
+    if let Some((severity, _)) = active(&rules.body_leading_blank, &()) {
+        if body.first().is_some_and(|x| !x.trim().is_empty()) {
+            report(
+                "body-leading-blank",
+                severity,
+                "body must be separated from the header by a blank line".to_owned(),
+            );
+        }
+    }
+    if let Some((severity, max)) =
+        active(&rules.body_max_line_length, &DEFAULT_BODY_MAX_LINE_LENGTH)
+    {
+        for (i, line) in body.iter().enumerate() {
+            let length = line.chars().count();
+            if length > *max {
+                report(
+                    "body-max-line-length",
+                    severity,
+                    format!(
+                        "line {} is {} characters long, the maximum is {}",
+                        i + 2,
+                        length,
+                        max
+                    ),
+                );
+            }
+        }
+    }
+
+    let Some(commit) = commit else {
+        return violations;
+    };
+    if let Some((severity, case)) = active(&rules.type_case, &Case::Lower) {
+        if !case.matches(commit.commit_type()) {
+            report("type-case", severity, format!("type must be {}", case));
+        }
+    }
+    if let Some((severity, case)) = active(&rules.subject_case, &Case::Lower) {
+        if !case.matches(commit.short_description()) {
+            report(
+                "subject-case",
+                severity,
+                format!("subject must be {}", case),
+            );
+        }
+    }
+    let full_stop = DEFAULT_FULL_STOP.to_owned();
+    if let Some((severity, stops)) = active(&rules.subject_full_stop, &full_stop) {
+        if commit
+            .short_description()
+            .trim_end()
+            .ends_with(|x| stops.contains(x))
+        {
+            report(
+                "subject-full-stop",
+                severity,
+                format!("subject must not end with any of {:?}", stops),
+            );
+        }
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lint;
+    use crate::config::{Case, Config, Rule, Rules, Severity};
+    use crate::utils::ConventionalCommit;
+
+    fn rule<T>(severity: Severity, value: Option<T>) -> Option<Rule<T>> {
+        Some(Rule { severity, value })
+    }
+
+    fn rules() -> Rules {
+        Rules {
+            header_max_length: rule(Severity::Error, Some(20)),
+            subject_case: rule(Severity::Warn, None),
+            subject_full_stop: rule(Severity::Error, None),
+            body_leading_blank: rule(Severity::Warn, None),
+            body_max_line_length: rule(Severity::Off, Some(5)),
+            type_case: rule(Severity::Error, Some(Case::Lower)),
+        }
+    }
+
+    fn rules_broken(message: &str) -> Vec<&'static str> {
+        let commit = ConventionalCommit::new(message, &Config::default(), String::new()).ok();
+        lint(message, commit.as_ref(), &rules())
+            .iter()
+            .map(|x| x.rule)
+            .collect()
+    }
+
+    #[test]
+    fn every_violation() {
+        assert_eq!(
+            rules_broken("feat: Add a new flag.\nbody"),
+            [
+                "header-max-length",
+                "body-leading-blank",
+                "subject-case",
+                "subject-full-stop"
+            ]
+        );
+        assert!(rules_broken("fix: typo\n\nlong body line").is_empty());
+        assert!(rules_broken("feat(cli): new flag").is_empty());
+    }
+
+    #[test]
+    fn unparsed_message() {
+        assert_eq!(
+            rules_broken("Not a conventional commit."),
+            ["header-max-length"]
+        );
+        assert!(lint("Too long for sure.", None, &Rules::default()).is_empty());
+    }
+
+    #[test]
+    fn case() {
+        assert!(Case::Lower.matches("add flag"));
+        assert!(!Case::Lower.matches("add HTTP flag"));
+        assert!(Case::Upper.matches("ADD FLAG"));
+        assert!(Case::Sentence.matches("Add flag"));
+        assert!(!Case::Sentence.matches("add flag"));
+    }
+}
@@ -0,0 +1,228 @@
+use crate::config::{Cascade, Package};
+use crate::utils::SemVerChangeType;
+use std::path::Path;
+use toml_edit::{DocumentMut, Item, TableLike};
+
+/// Dependency tables of a `Cargo.toml`, also found in `target.<cfg>` tables.
+const DEPENDENCY_TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// Dependency table left out of the dependency graph, as it is not published.
+const DEV_DEPENDENCIES: &str = "dev-dependencies";
+
+/// Returns the dependency tables of a `Cargo.toml`, the `target.<cfg>` ones
+/// included, along with their name, e.g. `dev-dependencies`.
+pub(crate) fn dependency_tables(doc: &mut DocumentMut) -> Vec<(String, &mut dyn TableLike)> {
+    let mut tables: Vec<(String, &mut dyn TableLike)> = Vec::new();
+    for (key, item) in doc.as_table_mut().iter_mut() {
+        if key.get() != "target" {
+            if DEPENDENCY_TABLES.contains(&key.get()) {
+                tables.extend(item.as_table_like_mut().map(|x| (key.get().to_owned(), x)));
+            }
+            continue;
+        }
+        let targets = item
+            .as_table_like_mut()
+            .into_iter()
+            .flat_map(|x| x.iter_mut());
+        for (_, target) in targets {
+            let target = target
+                .as_table_like_mut()
+                .into_iter()
+                .flat_map(|x| x.iter_mut());
+            for (key, item) in target {
+                if DEPENDENCY_TABLES.contains(&key.get()) {
+                    tables.extend(item.as_table_like_mut().map(|x| (key.get().to_owned(), x)));
+                }
+            }
+        }
+    }
+    tables
+}
+
+/// The parts of a package manifest needed to build the dependency graph.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Manifest {
+    /// Crate name, `package.name`.
+    pub name: String,
+    /// Names of the crates depended upon, renamed dependencies resolved.
+    pub dependencies: Vec<String>,
+}
+
+impl Manifest {
+    /// Parses the content of a `Cargo.toml`.
+    pub fn parse(content: &str) -> anyhow::Result<Self> {
+        let mut doc = content.parse::<DocumentMut>()?;
+        let Some(name) = doc
+            .get("package")
+            .and_then(|x| x.get("name"))
+            .and_then(Item::as_str)
+            .map(str::to_owned)
+        else {
+            return Err(anyhow::anyhow!("no package.name found"));
+        };
+
+        let mut dependencies: Vec<String> = Vec::new();
+        for (kind, table) in dependency_tables(&mut doc) {
+            if kind == DEV_DEPENDENCIES {
+                continue;
+            }
+            for (key, dependency) in table.iter() {
+                // `foo = { package = "bar" }` depends on `bar`
+                let name = dependency
+                    .get("package")
+                    .and_then(Item::as_str)
+                    .unwrap_or(key);
+                dependencies.push(name.to_owned());
+            }
+        }
+        Ok(Self { name, dependencies })
+    }
+
+    /// Reads the `Cargo.toml` found in `dir`, `None` when there is none.
+    pub fn read(dir: &Path) -> anyhow::Result<Option<Self>> {
+        let path = dir.join("Cargo.toml");
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| anyhow::anyhow!("couldn't read {}: {}", path.display(), e))?;
+        Self::parse(&content)
+            .map(Some)
+            .map_err(|e| anyhow::anyhow!("invalid manifest {}: {}", path.display(), e))
+    }
+}
+
+/// Internal dependencies between the packages of a monorepo, indexed like
+/// the `packages` they were built from.
+#[derive(Debug, PartialEq, Clone)]
+pub struct DependencyGraph {
+    /// `dependents[i]` holds the packages depending on package `i`.
+    dependents: Vec<Vec<usize>>,
+}
+
+impl DependencyGraph {
+    /// Builds the graph from the manifests of `packages`, read in their `path`.
+    /// Packages without a manifest have no internal dependencies.
+    pub fn from_packages(packages: &[Package]) -> anyhow::Result<Self> {
+        let manifests = packages
+            .iter()
+            .map(|x| Manifest::read(Path::new(&x.path)))
+            .collect::<anyhow::Result<Vec<Option<Manifest>>>>()?;
+        Ok(Self::from_manifests(&manifests))
+    }
+
+    /// Builds the graph linking every manifest to the manifests it depends on.
+    pub fn from_manifests(manifests: &[Option<Manifest>]) -> Self {
+        let mut dependents = vec![Vec::new(); manifests.len()];
+        for (dependent, manifest) in manifests.iter().enumerate() {
+            for dependency in manifest.iter().flat_map(|x| &x.dependencies) {
+                let found = manifests
+                    .iter()
+                    .position(|x| x.as_ref().is_some_and(|x| &x.name == dependency));
+                if let Some(index) = found {
+                    dependents[index].push(dependent);
+                }
+            }
+        }
+        Self { dependents }
+    }
+
+    /// Raises the `changes` of the packages depending on a released one,
+    /// transitively, according to `policy`.
+    ///
+    /// # Returns
+    /// The change of every package along with the index of the dependency
+    /// that raised it, `None` when the change comes from its own commits.
+    pub fn cascade(
+        &self,
+        changes: &[SemVerChangeType],
+        policy: &Cascade,
+    ) -> Vec<(SemVerChangeType, Option<usize>)> {
+        let mut result: Vec<(SemVerChangeType, Option<usize>)> =
+            changes.iter().map(|x| (x.clone(), None)).collect();
+        if *policy == Cascade::None {
+            return result;
+        }
+
+        // Changes only ever increase, so the walk ends once nothing is raised.
+        let mut pending: Vec<usize> = (0..changes.len()).collect();
+        while let Some(dependency) = pending.pop() {
+            let induced = match (policy, &result[dependency].0) {
+                (_, SemVerChangeType::None) => continue,
+                (Cascade::Match, change) => change.clone(),
+                _ => SemVerChangeType::Patch,
+            };
+            for &dependent in &self.dependents[dependency] {
+                if induced > result[dependent].0 {
+                    result[dependent] = (induced.clone(), Some(dependency));
+                    pending.push(dependent);
+                }
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DependencyGraph, Manifest};
+    use crate::config::Cascade;
+    use crate::utils::SemVerChangeType;
+
+    fn manifest(name: &str, dependencies: &[&str]) -> Option<Manifest> {
+        Some(Manifest {
+            name: name.to_owned(),
+            dependencies: dependencies.iter().map(|x| x.to_string()).collect(),
+        })
+    }
+
+    #[test]
+    fn parse() {
+        let manifest = Manifest::parse(
+            "[package]\nname = \"cli\"\n\n[dependencies]\ncore = { path = \"../core\", version = \"1.0\" }\nio = { package = \"my-io\", workspace = true }\n\n[dev-dependencies]\ntest-utils = \"1\"\n\n[target.'cfg(unix)'.dependencies]\nsys = { path = \"../sys\" }\n",
+        )
+        .unwrap();
+        assert_eq!(manifest.name, "cli");
+        assert_eq!(manifest.dependencies, vec!["core", "my-io", "sys"]);
+    }
+
+    #[test]
+    fn cascade() {
+        // app -> cli -> core, docs has no manifest
+        let graph = DependencyGraph::from_manifests(&[
+            manifest("core", &[]),
+            manifest("cli", &["core", "serde"]),
+            manifest("app", &["cli"]),
+            None,
+        ]);
+        let changes = [
+            SemVerChangeType::Minor,
+            SemVerChangeType::None,
+            SemVerChangeType::Patch,
+            SemVerChangeType::None,
+        ];
+
+        assert_eq!(
+            graph.cascade(&changes, &Cascade::Patch),
+            vec![
+                (SemVerChangeType::Minor, None),
+                (SemVerChangeType::Patch, Some(0)),
+                (SemVerChangeType::Patch, None),
+                (SemVerChangeType::None, None),
+            ]
+        );
+        assert_eq!(
+            graph.cascade(&changes, &Cascade::Match),
+            vec![
+                (SemVerChangeType::Minor, None),
+                (SemVerChangeType::Minor, Some(0)),
+                (SemVerChangeType::Minor, Some(1)),
+                (SemVerChangeType::None, None),
+            ]
+        );
+        assert_eq!(
+            graph.cascade(&changes, &Cascade::None)[1],
+            (SemVerChangeType::None, None)
+        );
+    }
+}
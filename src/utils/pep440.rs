@@ -0,0 +1,338 @@
+use crate::utils::version::VersionScheme;
+use crate::utils::SemVerChangeType;
+use anyhow::anyhow;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// Pre-release phase of a PEP 440 version.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Phase {
+    /// `a`, also spelled `alpha`.
+    Alpha,
+    /// `b`, also spelled `beta`.
+    Beta,
+    /// `rc`, also spelled `c`, `pre` or `preview`.
+    Rc,
+}
+
+impl FromStr for Phase {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.to_lowercase().as_str() {
+            "a" | "alpha" => Ok(Self::Alpha),
+            "b" | "beta" => Ok(Self::Beta),
+            "rc" | "c" | "pre" | "preview" => Ok(Self::Rc),
+            _ => Err(anyhow!(
+                "PEP 440 pre-releases are alpha, beta or rc, found {:?}",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Alpha => write!(f, "a"),
+            Self::Beta => write!(f, "b"),
+            Self::Rc => write!(f, "rc"),
+        }
+    }
+}
+
+/// A Python package version, see <https://peps.python.org/pep-0440/>.
+///
+/// Versions are rendered in their normalized form, e.g. `1.4.0rc2` for
+/// `1.4.0-RC.2`.
+#[derive(Debug, Clone)]
+pub struct Pep440 {
+    pub epoch: u128,
+    pub release: Vec<u128>,
+    pub pre: Option<(Phase, u128)>,
+    pub post: Option<u128>,
+    pub dev: Option<u128>,
+    pub local: Option<String>,
+}
+
+impl Pep440 {
+    /// Returns a final release made of the `release` segments.
+    pub fn new(release: Vec<u128>) -> Self {
+        Self {
+            epoch: 0,
+            release,
+            pre: None,
+            post: None,
+            dev: None,
+            local: None,
+        }
+    }
+
+    /// Returns the key this version is ordered by, following the PEP 440
+    /// rules: trailing zeros are not significant, `1.0.dev1 < 1.0a1 <
+    /// 1.0 < 1.0.post1`, and a local version sorts after its public one.
+    #[allow(clippy::type_complexity)]
+    fn key(
+        &self,
+    ) -> (
+        u128,
+        &[u128],
+        (u8, Option<(Phase, u128)>),
+        Option<u128>,
+        (u8, u128),
+        Vec<(u8, u128, String)>,
+    ) {
+        let significant =
+            self.release.len() - self.release.iter().rev().take_while(|x| **x == 0).count();
+        let pre = match (self.pre, self.post, self.dev) {
+            (None, None, Some(_)) => (0, None),
+            (Some(pre), _, _) => (1, Some(pre)),
+            (None, _, _) => (2, None),
+        };
+        let dev = self.dev.map_or((1, 0), |x| (0, x));
+        // Numeric local segments sort after the alphanumeric ones
+        let local = self
+            .local
+            .iter()
+            .flat_map(|x| x.split('.'))
+            .map(|x| match x.parse::<u128>() {
+                Ok(number) => (1, number, String::new()),
+                Err(_) => (0, 0, x.to_owned()),
+            })
+            .collect();
+        (
+            self.epoch,
+            &self.release[..significant],
+            pre,
+            self.post,
+            dev,
+            local,
+        )
+    }
+}
+
+impl PartialEq for Pep440 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Pep440 {}
+
+impl PartialOrd for Pep440 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Pep440 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl fmt::Display for Pep440 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.epoch != 0 {
+            write!(f, "{}!", self.epoch)?;
+        }
+        let release: Vec<String> = self.release.iter().map(u128::to_string).collect();
+        write!(f, "{}", release.join("."))?;
+        if let Some((phase, counter)) = self.pre {
+            write!(f, "{}{}", phase, counter)?;
+        }
+        if let Some(post) = self.post {
+            write!(f, ".post{}", post)?;
+        }
+        if let Some(dev) = self.dev {
+            write!(f, ".dev{}", dev)?;
+        }
+        if let Some(ref local) = self.local {
+            write!(f, "+{}", local)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Pep440 {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        lazy_static! {
+            // See https://peps.python.org/pep-0440/#appendix-b-parsing-version-strings-with-regular-expressions
+            static ref RE: Regex = Regex::new(r"(?i)^(?:(?P<epoch>[0-9]+)!)?(?P<release>[0-9]+(?:\.[0-9]+)*)(?:[-_.]?(?P<pre_l>a|b|c|rc|alpha|beta|pre|preview)[-_.]?(?P<pre_n>[0-9]+)?)?(?:-(?P<post_n1>[0-9]+)|[-_.]?(?P<post_l>post|rev|r)[-_.]?(?P<post_n2>[0-9]+)?)?(?:[-_.]?(?P<dev_l>dev)[-_.]?(?P<dev_n>[0-9]+)?)?(?:\+(?P<local>[a-z0-9]+(?:[-_.][a-z0-9]+)*))?$").unwrap();
+        }
+
+        let Some(captures) = RE.captures(s) else {
+            return Err(anyhow!("{:?} is not a PEP 440 version", s));
+        };
+        // Implicit numbers are 0, e.g. `1.0rc` is `1.0rc0`
+        let number = |name: &str| -> anyhow::Result<u128> {
+            Ok(captures.name(name).map_or(Ok(0), |x| x.as_str().parse())?)
+        };
+
+        let pre = match captures.name("pre_l") {
+            Some(phase) => Some((phase.as_str().parse::<Phase>()?, number("pre_n")?)),
+            None => None,
+        };
+        let post = match (captures.name("post_n1"), captures.name("post_l")) {
+            (Some(_), _) => Some(number("post_n1")?),
+            (None, Some(_)) => Some(number("post_n2")?),
+            (None, None) => None,
+        };
+        let dev = match captures.name("dev_l") {
+            Some(_) => Some(number("dev_n")?),
+            None => None,
+        };
+        Ok(Self {
+            epoch: number("epoch")?,
+            release: captures["release"]
+                .split('.')
+                .map(str::parse::<u128>)
+                .collect::<Result<Vec<u128>, _>>()?,
+            pre,
+            post,
+            dev,
+            local: captures
+                .name("local")
+                .map(|x| x.as_str().to_lowercase().replace(['-', '_'], ".")),
+        })
+    }
+}
+
+impl VersionScheme for Pep440 {
+    fn parse(text: &str) -> Option<Self> {
+        text.parse().ok()
+    }
+
+    fn initial() -> Self {
+        Self::new(vec![0, 0, 0])
+    }
+
+    fn bump(&self, change: SemVerChangeType) -> Self {
+        let segment = |i: usize| self.release.get(i).copied().unwrap_or_default();
+        let (major, minor, micro) = (segment(0), segment(1), segment(2));
+        let release = match change {
+            SemVerChangeType::Major => vec![major + 1, 0, 0],
+            SemVerChangeType::Minor => vec![major, minor + 1, 0],
+            SemVerChangeType::Patch => vec![major, minor, micro + 1],
+            SemVerChangeType::None => return self.clone(),
+        };
+        Self {
+            epoch: self.epoch,
+            ..Self::new(release)
+        }
+    }
+
+    fn release(&self) -> Self {
+        Self {
+            epoch: self.epoch,
+            ..Self::new(self.release.clone())
+        }
+    }
+
+    fn is_initial_development(&self) -> bool {
+        self.release.first() == Some(&0)
+    }
+
+    fn is_prerelease(&self) -> bool {
+        self.pre.is_some() || self.dev.is_some()
+    }
+
+    fn with_pre_release(&self, channel: &str, counter: u128) -> anyhow::Result<Self> {
+        Ok(Self {
+            pre: Some((channel.parse()?, counter)),
+            ..self.release()
+        })
+    }
+
+    fn pre_release_counter(&self, channel: &str) -> Option<u128> {
+        let phase = channel.parse::<Phase>().ok()?;
+        match (self.pre, self.post, self.dev) {
+            (Some((found, counter)), None, None) if found == phase => Some(counter),
+            _ => None,
+        }
+    }
+
+    fn with_dev(&self, counter: u128) -> Self {
+        Self {
+            dev: Some(counter),
+            ..self.release()
+        }
+    }
+
+    fn with_local(&self, local: &str) -> Self {
+        Self {
+            local: Some(local.to_lowercase().replace(['-', '_'], ".")),
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pep440;
+    use crate::utils::version::VersionScheme;
+    use crate::utils::SemVerChangeType;
+
+    fn version(text: &str) -> Pep440 {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn normalize() {
+        for (text, normalized) in [
+            ("1.4.0rc2", "1.4.0rc2"),
+            ("1.4.0-RC.2", "1.4.0rc2"),
+            ("1.4.0.post1", "1.4.0.post1"),
+            ("1.4.0-1", "1.4.0.post1"),
+            ("2!1.0alpha", "2!1.0a0"),
+            ("1.0.dev3+Ubuntu-1", "1.0.dev3+ubuntu.1"),
+        ] {
+            assert_eq!(version(text).to_string(), normalized);
+        }
+        assert!("1.4.0-nightly".parse::<Pep440>().is_err());
+    }
+
+    #[test]
+    fn precedence() {
+        let ordered = [
+            "1.0.dev1",
+            "1.0a1.dev1",
+            "1.0a1",
+            "1.0b2.post3",
+            "1.0rc1",
+            "1.0",
+            "1.0.0+local.7",
+            "1.0.post1.dev1",
+            "1.0.post1",
+            "1.1",
+            "1!0.1",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(version(pair[0]) < version(pair[1]), "{:?}", pair);
+        }
+        assert_eq!(version("1.0"), version("1.0.0"));
+    }
+
+    #[test]
+    fn scheme() {
+        let latest = version("1.3.2.post1");
+        assert_eq!(latest.bump(SemVerChangeType::Minor).to_string(), "1.4.0");
+        let rc = latest
+            .bump(SemVerChangeType::Minor)
+            .with_pre_release("rc", 2)
+            .unwrap();
+        assert_eq!(rc.to_string(), "1.4.0rc2");
+        assert_eq!(rc.pre_release_counter("rc"), Some(2));
+        assert_eq!(rc.pre_release_counter("beta"), None);
+        assert_eq!(
+            rc.release().with_dev(12).with_local("gabc").to_string(),
+            "1.4.0.dev12+gabc"
+        );
+        assert!(!version("1.4.0.post1").is_prerelease());
+    }
+}
@@ -0,0 +1,137 @@
+use colored::Colorize;
+use log::info;
+use std::path::Path;
+
+/// Reports an action skipped because of `--dry-run`.
+///
+/// # Example
+/// `report("create tag 1.2.0 on HEAD")` prints `dry-run: would create tag 1.2.0 on HEAD`
+pub fn report(action: &str) {
+    println!("{} would {}", "dry-run:".yellow(), action);
+}
+
+/// Writes `contents` to `path`, creating the file when missing.
+///
+/// When `dry_run` is `true` nothing is written: a diff between the current
+/// content of `path` and `contents` is printed instead.
+pub fn write_file(path: &Path, contents: &str, dry_run: bool) -> anyhow::Result<()> {
+    if !dry_run {
+        std::fs::write(path, contents)
+            .map_err(|e| anyhow::anyhow!("couldn't write {}: {}", path.display(), e))?;
+        info!("{} written", path.display());
+        return Ok(());
+    }
+
+    match std::fs::read_to_string(path).ok() {
+        Some(ref current) if current == contents => {
+            report(&format!("leave {} unchanged", path.display()));
+        }
+        Some(ref current) => {
+            let name = path.display().to_string();
+            report(&format!("update {}:", name));
+            print!("{}", diff(current, contents, &name, &name));
+        }
+        None => {
+            report(&format!("create {}:", path.display()));
+            print!(
+                "{}",
+                diff("", contents, "/dev/null", &path.display().to_string())
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Renders a unified diff of `old` and `new` as a single hunk spanning the
+/// lines between the first and the last change.
+fn diff(old: &str, new: &str, old_name: &str, new_name: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+
+    // Trim the common prefix and suffix so the LCS only runs on the changed part.
+    let prefix = old_lines
+        .iter()
+        .zip(&new_lines)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old_lines[prefix..old_lines.len() - suffix];
+    let new_mid = &new_lines[prefix..new_lines.len() - suffix];
+
+    // lcs[i][j] is the length of the longest common subsequence of
+    // old_mid[i..] and new_mid[j..].
+    let mut lcs = vec![vec![0usize; new_mid.len() + 1]; old_mid.len() + 1];
+    for i in (0..old_mid.len()).rev() {
+        for j in (0..new_mid.len()).rev() {
+            lcs[i][j] = if old_mid[i] == new_mid[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    // An empty range is numbered after the line preceding it, as in `diff -u`.
+    let start = |len: usize| if len == 0 { prefix } else { prefix + 1 };
+    let mut out = format!(
+        "--- {}\n+++ {}\n@@ -{},{} +{},{} @@\n",
+        old_name,
+        new_name,
+        start(old_mid.len()),
+        old_mid.len(),
+        start(new_mid.len()),
+        new_mid.len()
+    );
+    let (mut i, mut j) = (0, 0);
+    while i < old_mid.len() || j < new_mid.len() {
+        if i < old_mid.len() && j < new_mid.len() && old_mid[i] == new_mid[j] {
+            out.push_str(&format!(" {}\n", old_mid[i]));
+            i += 1;
+            j += 1;
+        } else if i < old_mid.len() && (j == new_mid.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            out.push_str(&format!("{}\n", format!("-{}", old_mid[i]).red()));
+            i += 1;
+        } else {
+            out.push_str(&format!("{}\n", format!("+{}", new_mid[j]).green()));
+            j += 1;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::diff;
+
+    #[test]
+    fn single_line_change() {
+        colored::control::set_override(false);
+        assert_eq!(
+            diff("a\nversion = 1\nb\n", "a\nversion = 2\nb\n", "f", "f"),
+            "--- f\n+++ f\n@@ -2,1 +2,1 @@\n-version = 1\n+version = 2\n"
+        );
+    }
+
+    #[test]
+    fn keeps_common_lines_in_hunk() {
+        colored::control::set_override(false);
+        assert_eq!(
+            diff("x\nkeep\ny\n", "keep\nz\n", "f", "f"),
+            "--- f\n+++ f\n@@ -1,3 +1,2 @@\n-x\n keep\n-y\n+z\n"
+        );
+    }
+
+    #[test]
+    fn new_file() {
+        colored::control::set_override(false);
+        assert_eq!(
+            diff("", "a\nb\n", "/dev/null", "f"),
+            "--- /dev/null\n+++ f\n@@ -0,0 +1,2 @@\n+a\n+b\n"
+        );
+    }
+}
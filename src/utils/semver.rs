@@ -2,10 +2,13 @@ use anyhow::{anyhow, Result};
 use core::fmt::Display;
 use lazy_static::lazy_static;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::cmp::min;
 use std::cmp::Ordering;
 use std::str::FromStr;
 
+use crate::utils::version::VersionScheme;
+
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub struct SemVer {
     pub major: u128,
@@ -15,7 +18,8 @@ pub struct SemVer {
     pub build_meta: Option<String>,
 }
 
-#[derive(Debug, PartialEq, Clone, PartialOrd, Ord, Eq)]
+#[derive(Debug, PartialEq, Clone, PartialOrd, Ord, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SemVerChangeType {
     None,
     Patch,
@@ -23,6 +27,17 @@ pub enum SemVerChangeType {
     Major,
 }
 
+impl Display for SemVerChangeType {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Patch => write!(f, "patch"),
+            Self::Minor => write!(f, "minor"),
+            Self::Major => write!(f, "major"),
+        }
+    }
+}
+
 impl PartialOrd for SemVer {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -130,6 +145,71 @@ impl Display for SemVer {
     }
 }
 
+/// Pre-release identifier of development versions, e.g. `1.3.0-dev.4`.
+const DEV_CHANNEL: &str = "dev";
+
+impl VersionScheme for SemVer {
+    fn parse(text: &str) -> Option<Self> {
+        text.parse().ok()
+    }
+
+    fn initial() -> Self {
+        Self::new(0, 0, 0, None, None)
+    }
+
+    fn bump(&self, change: SemVerChangeType) -> Self {
+        match change {
+            SemVerChangeType::Major => Self::new(self.major + 1, 0, 0, None, None),
+            SemVerChangeType::Minor => Self::new(self.major, self.minor + 1, 0, None, None),
+            SemVerChangeType::Patch => {
+                Self::new(self.major, self.minor, self.patch + 1, None, None)
+            }
+            SemVerChangeType::None => self.clone(),
+        }
+    }
+
+    fn release(&self) -> Self {
+        Self::new(self.major, self.minor, self.patch, None, None)
+    }
+
+    fn is_initial_development(&self) -> bool {
+        self.major == 0
+    }
+
+    fn is_prerelease(&self) -> bool {
+        self.pre_release.is_some()
+    }
+
+    fn with_pre_release(&self, channel: &str, counter: u128) -> anyhow::Result<Self> {
+        Ok(Self {
+            pre_release: Some(format!("{}.{}", channel, counter)),
+            ..self.release()
+        })
+    }
+
+    fn pre_release_counter(&self, channel: &str) -> Option<u128> {
+        let (identifier, counter) = self.pre_release.as_deref()?.rsplit_once('.')?;
+        match identifier == channel {
+            true => counter.parse().ok(),
+            false => None,
+        }
+    }
+
+    fn with_dev(&self, counter: u128) -> Self {
+        Self {
+            pre_release: Some(format!("{}.{}", DEV_CHANNEL, counter)),
+            ..self.release()
+        }
+    }
+
+    fn with_local(&self, local: &str) -> Self {
+        Self {
+            build_meta: Some(local.to_owned()),
+            ..self.clone()
+        }
+    }
+}
+
 impl FromStr for SemVer {
     type Err = anyhow::Error;
 
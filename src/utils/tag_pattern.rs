@@ -0,0 +1,105 @@
+use crate::config::{Config, Package};
+use std::fmt::Display;
+
+/// Placeholder replaced by the version inside a tag pattern.
+pub const VERSION_PLACEHOLDER: &str = "{version}";
+
+/// Placeholder replaced by the package name inside a tag pattern.
+pub const PACKAGE_PLACEHOLDER: &str = "{package}";
+
+/// Tag pattern of the monorepo packages without a `tag_prefix`.
+pub const DEFAULT_PACKAGE_PATTERN: &str = "{package}@{version}";
+
+/// Maps versions to git tag names and back.
+///
+/// A pattern is either a plain prefix (e.g. `v`, giving `v1.2.3`) or a
+/// template containing `{version}` and optionally `{package}`
+/// (e.g. `{package}@{version}`, giving `mypkg@1.2.3`).
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct TagPattern {
+    prefix: String,
+    suffix: String,
+}
+
+impl TagPattern {
+    /// Builds a pattern from its textual form, substituting `{package}` with
+    /// `package` (or an empty string when `None`).
+    pub fn new(pattern: &str, package: Option<&str>) -> Self {
+        let pattern = pattern.replace(PACKAGE_PLACEHOLDER, package.unwrap_or_default());
+        match pattern.split_once(VERSION_PLACEHOLDER) {
+            Some((prefix, suffix)) => Self {
+                prefix: prefix.to_owned(),
+                suffix: suffix.to_owned(),
+            },
+            None => Self {
+                prefix: pattern,
+                suffix: String::new(),
+            },
+        }
+    }
+
+    /// Builds the pattern configured in `tag.prefix`, defaulting to bare versions.
+    pub fn from_config(config: &Config) -> Self {
+        match config.tag {
+            Some(ref tag) => match tag.prefix {
+                Some(ref prefix) => Self::new(prefix, tag.package.as_deref()),
+                None => Self::default(),
+            },
+            None => Self::default(),
+        }
+    }
+
+    /// Builds the pattern of a monorepo `package`, defaulting to
+    /// [`DEFAULT_PACKAGE_PATTERN`].
+    pub fn from_package(package: &Package) -> Self {
+        Self::new(
+            package
+                .tag_prefix
+                .as_deref()
+                .unwrap_or(DEFAULT_PACKAGE_PATTERN),
+            Some(&package.name),
+        )
+    }
+
+    /// Returns the version part of `tag`, or `None` when the tag does not
+    /// match the pattern.
+    pub fn strip<'a>(&self, tag: &'a str) -> Option<&'a str> {
+        tag.strip_prefix(&self.prefix)?.strip_suffix(&self.suffix)
+    }
+
+    /// Returns the tag name of `version`.
+    pub fn format(&self, version: &impl Display) -> String {
+        format!("{}{}{}", self.prefix, version, self.suffix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TagPattern;
+    use crate::utils::semver::SemVer;
+
+    #[test]
+    fn plain_prefix() {
+        let pattern = TagPattern::new("v", None);
+        let version = "1.2.3".parse::<SemVer>().unwrap();
+        assert_eq!(pattern.strip("v1.2.3"), Some("1.2.3"));
+        assert_eq!(pattern.strip("1.2.3"), None);
+        assert_eq!(pattern.format(&version), "v1.2.3");
+    }
+
+    #[test]
+    fn package_template() {
+        let pattern = TagPattern::new("{package}@{version}", Some("mypkg"));
+        let version = "1.2.3-rc.1".parse::<SemVer>().unwrap();
+        assert_eq!(pattern.strip("mypkg@1.2.3-rc.1"), Some("1.2.3-rc.1"));
+        assert_eq!(pattern.strip("otherpkg@1.2.3"), None);
+        assert_eq!(pattern.format(&version), "mypkg@1.2.3-rc.1");
+    }
+
+    #[test]
+    fn no_prefix() {
+        let pattern = TagPattern::default();
+        assert_eq!(pattern.strip("v1.2.3"), Some("v1.2.3"));
+        assert_eq!(pattern.format(&"1.2.3".parse::<SemVer>().unwrap()), "1.2.3");
+    }
+}
@@ -0,0 +1,44 @@
+use crate::utils::SemVerChangeType;
+use std::fmt::{Debug, Display};
+
+/// A versioning scheme: how its versions are parsed, ordered and bumped.
+///
+/// Implemented by [`SemVer`](crate::utils::semver::SemVer) and
+/// [`Pep440`](crate::utils::pep440::Pep440), ordering is the one of [`Ord`].
+pub trait VersionScheme: Sized + Clone + Ord + Display + Debug {
+    /// Parses `text`, `None` when it is not a version of the scheme.
+    fn parse(text: &str) -> Option<Self>;
+
+    /// Version preceding the first release, `0.0.0`.
+    fn initial() -> Self;
+
+    /// Returns the stable version following this one for a `change`, e.g.
+    /// `1.3.0` for a minor change after `1.2.4`. `None` returns it unchanged.
+    fn bump(&self, change: SemVerChangeType) -> Self;
+
+    /// Returns the stable version this one leads to or derives from, e.g.
+    /// `1.3.0` for `1.3.0-rc.2`.
+    fn release(&self) -> Self;
+
+    /// Returns `true` while the major version is 0, the initial development.
+    fn is_initial_development(&self) -> bool;
+
+    /// Returns `true` for pre-releases and development releases.
+    fn is_prerelease(&self) -> bool;
+
+    /// Returns the `counter`-th pre-release of `channel` leading to the
+    /// release of this version, e.g. `1.3.0-rc.2` for `rc` and 2.
+    fn with_pre_release(&self, channel: &str, counter: u128) -> anyhow::Result<Self>;
+
+    /// Returns the counter of this version when it is a pre-release of
+    /// `channel`, e.g. 2 for `1.3.0-rc.2` and `rc`.
+    fn pre_release_counter(&self, channel: &str) -> Option<u128>;
+
+    /// Returns the `counter`-th development release leading to the release
+    /// of this version.
+    fn with_dev(&self, counter: u128) -> Self;
+
+    /// Returns this version labelled with `local`, the build metadata of
+    /// SemVer or the local version label of PEP 440.
+    fn with_local(&self, local: &str) -> Self;
+}
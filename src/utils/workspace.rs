@@ -0,0 +1,425 @@
+use crate::utils::manifest::{dependency_tables, Manifest};
+use crate::utils::semver::SemVer;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item, TableLike, Value};
+
+/// A Cargo workspace, released by rewriting the manifests of its members and
+/// its `Cargo.lock` in step.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Workspace {
+    /// Directory holding the root `Cargo.toml` and the `Cargo.lock`.
+    pub root: PathBuf,
+    /// Directories of the member crates, the root included when it is one.
+    pub members: Vec<PathBuf>,
+}
+
+/// Reads a file to a string, with a readable error.
+fn read(path: &Path) -> anyhow::Result<String> {
+    std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("couldn't read {}: {}", path.display(), e))
+}
+
+/// Replaces the string `value`, keeping its surrounding whitespace and comments.
+fn set_str(value: &mut Value, new: String) {
+    let decor = value.decor().clone();
+    *value = Value::from(new);
+    *value.decor_mut() = decor;
+}
+
+/// Returns the string value at `key` of `table`, if any.
+fn str_mut<'a>(table: &'a mut dyn TableLike, key: &str) -> Option<&'a mut Value> {
+    table.get_mut(key)?.as_value_mut().filter(|x| x.is_str())
+}
+
+/// Rewrites the version requirement `old` to require `version`, keeping the
+/// operator and the precision of each comparator, e.g. `^1.2` becomes `^1.3`
+/// for `1.3.0`. Upper bounds (`<`, `<=`) are kept as long as `version` meets
+/// them. Pre-releases are always required in full.
+///
+/// # Errors
+/// - Returns [`Err`] if an upper bound of `old` excludes `version`.
+/// - Returns [`Err`] if a comparator is not a plain version, e.g. `1.*`.
+pub fn requirement(old: &str, version: &SemVer) -> anyhow::Result<String> {
+    let comparators = old
+        .split(',')
+        .map(|x| {
+            // Keep the spacing around the commas
+            let start = x.len() - x.trim_start().len();
+            let end = x.trim_end().len();
+            let new = comparator(&x[start..end], version).ok_or(anyhow::anyhow!(
+                "can't rewrite the requirement {:?} to {}",
+                old,
+                version
+            ))?;
+            Ok(format!("{}{}{}", &x[..start], new, &x[end..]))
+        })
+        .collect::<anyhow::Result<Vec<String>>>()?;
+    Ok(comparators.join(","))
+}
+
+/// Rewrites the single comparator `old` of a requirement to require
+/// `version`, `None` when it can't.
+fn comparator(old: &str, version: &SemVer) -> Option<String> {
+    let operator = &old[..old.len() - old.trim_start_matches(['=', '^', '~', '>', '<', ' ']).len()];
+    let numbers = old[operator.len()..]
+        .split(['-', '+'])
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map(|x| x.parse::<u128>().ok())
+        .collect::<Option<Vec<u128>>>()?;
+    let parts = [version.major, version.minor, version.patch];
+    if operator.starts_with('<') {
+        let current = &parts[..numbers.len().clamp(1, 3)];
+        let met = match operator.contains('=') {
+            true => current <= &numbers[..],
+            false => current < &numbers[..],
+        };
+        return met.then(|| old.to_owned());
+    }
+    if version.pre_release.is_some() {
+        let version = SemVer {
+            build_meta: None,
+            ..version.clone()
+        };
+        return Some(format!("{}{}", operator, version));
+    }
+    let parts: Vec<String> = parts
+        .iter()
+        .take(numbers.len().clamp(1, 3))
+        .map(u128::to_string)
+        .collect();
+    Some(format!("{}{}", operator, parts.join(".")))
+}
+
+/// Rewrites the path dependencies of `table` found in `versions`.
+fn update_dependencies(
+    table: &mut dyn TableLike,
+    versions: &BTreeMap<String, SemVer>,
+) -> anyhow::Result<()> {
+    for (key, dependency) in table.iter_mut() {
+        let Some(dependency) = dependency.as_table_like_mut() else {
+            continue;
+        };
+        if !dependency.contains_key("path") {
+            continue;
+        }
+        let name = dependency
+            .get("package")
+            .and_then(Item::as_str)
+            .unwrap_or(key.get())
+            .to_owned();
+        if let (Some(version), Some(old)) = (versions.get(&name), str_mut(dependency, "version")) {
+            let new = requirement(old.as_str().unwrap_or_default(), version)?;
+            set_str(old, new);
+        }
+    }
+    Ok(())
+}
+
+/// Returns `content`, the text of a `Cargo.toml`, releasing `versions`.
+///
+/// # Arguments
+/// - `content`           – Text of the manifest.
+///
+/// - `versions`          – New version of every released crate, by crate name.
+///
+/// - `workspace_version` – New `workspace.package.version`, when released.
+pub fn update_manifest(
+    content: &str,
+    versions: &BTreeMap<String, SemVer>,
+    workspace_version: Option<&SemVer>,
+) -> anyhow::Result<String> {
+    let mut doc = content.parse::<DocumentMut>()?;
+
+    if let Some(package) = doc.get_mut("package").and_then(Item::as_table_like_mut) {
+        let name = package
+            .get("name")
+            .and_then(Item::as_str)
+            .unwrap_or_default();
+        if let Some(version) = versions.get(name).cloned() {
+            // `version.workspace = true` is not a string and is left as is
+            if let Some(old) = str_mut(package, "version") {
+                set_str(old, version.to_string());
+            }
+        }
+    }
+
+    if let Some(workspace) = doc.get_mut("workspace").and_then(Item::as_table_like_mut) {
+        let package = workspace
+            .get_mut("package")
+            .and_then(Item::as_table_like_mut);
+        if let (Some(package), Some(version)) = (package, workspace_version) {
+            if let Some(old) = str_mut(package, "version") {
+                set_str(old, version.to_string());
+            }
+        }
+        if let Some(table) = workspace
+            .get_mut("dependencies")
+            .and_then(Item::as_table_like_mut)
+        {
+            update_dependencies(table, versions)?;
+        }
+    }
+
+    // The path dependencies of every table get their requirement rewritten
+    for (_, table) in dependency_tables(&mut doc) {
+        update_dependencies(table, versions)?;
+    }
+
+    Ok(doc.to_string())
+}
+
+/// Returns `content`, the text of a `Cargo.lock`, with the local packages
+/// found in `versions` released. Registry and git packages are left as is, so
+/// no fetch is needed for `cargo build --locked` to pass.
+pub fn update_lockfile(
+    content: &str,
+    versions: &BTreeMap<String, SemVer>,
+) -> anyhow::Result<String> {
+    let mut doc = content.parse::<DocumentMut>()?;
+    let Some(packages) = doc
+        .get_mut("package")
+        .and_then(Item::as_array_of_tables_mut)
+    else {
+        return Ok(content.to_owned());
+    };
+
+    // Local packages have no `source`
+    let mut renamed: BTreeMap<String, String> = BTreeMap::new();
+    for package in packages.iter_mut().filter(|x| !x.contains_key("source")) {
+        let name = package
+            .get("name")
+            .and_then(Item::as_str)
+            .unwrap_or_default()
+            .to_owned();
+        let Some(version) = versions.get(&name) else {
+            continue;
+        };
+        if let Some(old) = str_mut(package, "version") {
+            let from = format!("{} {}", name, old.as_str().unwrap_or_default());
+            renamed.insert(from, format!("{} {}", name, version));
+            set_str(old, version.to_string());
+        }
+    }
+
+    // Dependencies are only written as `name version` when ambiguous
+    for package in packages.iter_mut() {
+        let Some(dependencies) = package.get_mut("dependencies").and_then(Item::as_array_mut)
+        else {
+            continue;
+        };
+        for dependency in dependencies.iter_mut() {
+            if let Some(new) = dependency.as_str().and_then(|x| renamed.get(x)).cloned() {
+                set_str(dependency, new);
+            }
+        }
+    }
+    Ok(doc.to_string())
+}
+
+impl Workspace {
+    /// Reads the workspace whose root manifest is in `root`, `None` when it
+    /// has no `[workspace]` table.
+    ///
+    /// Members are read from `workspace.members`, globs are only supported as
+    /// the last path component (e.g. `crates/*`).
+    pub fn read(root: &Path) -> anyhow::Result<Option<Self>> {
+        let path = root.join("Cargo.toml");
+        if !path.exists() {
+            return Ok(None);
+        }
+        let doc = read(&path)?.parse::<DocumentMut>()?;
+        let Some(workspace) = doc.get("workspace") else {
+            return Ok(None);
+        };
+
+        let mut members: Vec<PathBuf> = Vec::new();
+        if doc.contains_key("package") {
+            members.push(root.to_path_buf());
+        }
+        let patterns = workspace
+            .get("members")
+            .and_then(Item::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str);
+        for pattern in patterns {
+            match pattern.strip_suffix("/*") {
+                Some(parent) => {
+                    let mut found: Vec<PathBuf> = std::fs::read_dir(root.join(parent))?
+                        .filter_map(|x| x.ok().map(|x| x.path()))
+                        .filter(|x| x.join("Cargo.toml").exists())
+                        .collect();
+                    found.sort();
+                    members.extend(found);
+                }
+                None => members.push(root.join(pattern)),
+            }
+        }
+        Ok(Some(Self {
+            root: root.to_path_buf(),
+            members,
+        }))
+    }
+
+    /// Returns the crate name of every member.
+    pub fn crates(&self) -> anyhow::Result<Vec<String>> {
+        let mut crates = Vec::new();
+        for member in &self.members {
+            match Manifest::read(member)? {
+                Some(manifest) => crates.push(manifest.name),
+                None => {
+                    return Err(anyhow::anyhow!(
+                        "no Cargo.toml found in member {}",
+                        member.display()
+                    ))
+                }
+            }
+        }
+        Ok(crates)
+    }
+
+    /// Returns the version of the members inheriting `workspace.package.version`
+    /// released in `versions`, by crate name.
+    ///
+    /// # Errors
+    /// - Returns [`Err`] if they are released with different versions.
+    pub fn inherited_version(
+        &self,
+        versions: &BTreeMap<String, SemVer>,
+    ) -> anyhow::Result<Option<SemVer>> {
+        let mut inherited_version: Option<&SemVer> = None;
+        for member in &self.members {
+            let doc = read(&member.join("Cargo.toml"))?.parse::<DocumentMut>()?;
+            let package = doc.get("package");
+            let inherited = package
+                .and_then(|x| x.get("version"))
+                .and_then(|x| x.get("workspace"))
+                .and_then(Item::as_bool)
+                .unwrap_or_default();
+            let name = package.and_then(|x| x.get("name")).and_then(Item::as_str);
+            let Some(version) = name.and_then(|x| versions.get(x)).filter(|_| inherited) else {
+                continue;
+            };
+            match inherited_version {
+                Some(other) if other != version => {
+                    return Err(anyhow::anyhow!(
+                        "members inheriting workspace.package.version must be released in step, found {} and {}",
+                        other,
+                        version
+                    ));
+                }
+                _ => inherited_version = Some(version),
+            }
+        }
+        Ok(inherited_version.cloned())
+    }
+
+    /// Computes the new content of every manifest of the workspace and of its
+    /// `Cargo.lock` to release `versions`, by crate name.
+    ///
+    /// # Arguments
+    /// - `versions`          – New version of every released crate, by crate name.
+    ///
+    /// - `workspace_version` – New `workspace.package.version`, left as is when `None`.
+    ///
+    /// # Returns
+    /// The path and the new content of every file, changed or not.
+    pub fn release(
+        &self,
+        versions: &BTreeMap<String, SemVer>,
+        workspace_version: Option<&SemVer>,
+    ) -> anyhow::Result<Vec<(PathBuf, String)>> {
+        let mut manifests: Vec<PathBuf> =
+            self.members.iter().map(|x| x.join("Cargo.toml")).collect();
+        let root = self.root.join("Cargo.toml");
+        if !manifests.contains(&root) {
+            manifests.insert(0, root);
+        }
+
+        let mut files = Vec::new();
+        for path in manifests {
+            let content = update_manifest(&read(&path)?, versions, workspace_version)?;
+            files.push((path, content));
+        }
+        let lockfile = self.root.join("Cargo.lock");
+        if lockfile.exists() {
+            let content = update_lockfile(&read(&lockfile)?, versions)?;
+            files.push((lockfile, content));
+        }
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{requirement, update_lockfile, update_manifest};
+    use crate::utils::semver::SemVer;
+    use std::collections::BTreeMap;
+
+    fn versions(version: &str) -> BTreeMap<String, SemVer> {
+        BTreeMap::from([("core".to_owned(), version.parse::<SemVer>().unwrap())])
+    }
+
+    #[test]
+    fn requirements() {
+        let version = "1.3.0".parse::<SemVer>().unwrap();
+        let requirement = |old: &str, version: &SemVer| requirement(old, version).unwrap();
+        assert_eq!(requirement("1.2", &version), "1.3");
+        assert_eq!(requirement("=1.2.0", &version), "=1.3.0");
+        assert_eq!(requirement("^1", &version), "^1");
+        let version = "2.0.0-rc.1".parse::<SemVer>().unwrap();
+        assert_eq!(requirement("~1.2", &version), "~2.0.0-rc.1");
+    }
+
+    /// Each comparator is rewritten, upper bounds are kept while they hold.
+    #[test]
+    fn multiple_comparators() {
+        let version = "1.3.0".parse::<SemVer>().unwrap();
+        assert_eq!(requirement(">=1.2, <2", &version).unwrap(), ">=1.3, <2");
+        assert_eq!(
+            requirement(">= 1.2.0,<=1.3", &version).unwrap(),
+            ">= 1.3.0,<=1.3"
+        );
+        assert!(requirement(">=1.2, <1.3", &version).is_err());
+        assert!(requirement("1.*", &version).is_err());
+    }
+
+    #[test]
+    fn manifest() {
+        let content = "[package]\nname = \"core\"\nversion = \"1.2.0\" # keep\n\n[dependencies]\ncore-macros = { path = \"macros\", version = \"1.2\" }\nserde = { version = \"1.0\" }\n\n[target.'cfg(unix)'.dev-dependencies]\nio = { package = \"core\", path = \"..\", version = \"=1.2.0\" }\n";
+        let mut versions = versions("1.3.0");
+        versions.insert("core-macros".to_owned(), "1.2.1".parse().unwrap());
+        assert_eq!(
+            update_manifest(content, &versions, None).unwrap(),
+            "[package]\nname = \"core\"\nversion = \"1.3.0\" # keep\n\n[dependencies]\ncore-macros = { path = \"macros\", version = \"1.2\" }\nserde = { version = \"1.0\" }\n\n[target.'cfg(unix)'.dev-dependencies]\nio = { package = \"core\", path = \"..\", version = \"=1.3.0\" }\n"
+        );
+    }
+
+    #[test]
+    fn workspace_manifest() {
+        let content = "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\nversion = \"1.2.0\"\n\n[workspace.dependencies]\ncore = { path = \"crates/core\", version = \"1.2.0\" }\nserde = \"1\"\n";
+        let version = "1.3.0".parse::<SemVer>().unwrap();
+        assert_eq!(
+            update_manifest(content, &versions("1.3.0"), Some(&version)).unwrap(),
+            "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\nversion = \"1.3.0\"\n\n[workspace.dependencies]\ncore = { path = \"crates/core\", version = \"1.3.0\" }\nserde = \"1\"\n"
+        );
+        // members inheriting the version are left as is
+        let member = "[package]\nname = \"core\"\nversion.workspace = true\n";
+        assert_eq!(
+            update_manifest(member, &versions("1.3.0"), Some(&version)).unwrap(),
+            member
+        );
+    }
+
+    #[test]
+    fn lockfile() {
+        let content = "version = 4\n\n[[package]]\nname = \"app\"\nversion = \"0.1.0\"\ndependencies = [\n \"core 1.2.0\",\n \"serde\",\n]\n\n[[package]]\nname = \"core\"\nversion = \"1.2.0\"\n\n[[package]]\nname = \"core\"\nversion = \"0.9.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n";
+        assert_eq!(
+            update_lockfile(content, &versions("1.3.0")).unwrap(),
+            "version = 4\n\n[[package]]\nname = \"app\"\nversion = \"0.1.0\"\ndependencies = [\n \"core 1.3.0\",\n \"serde\",\n]\n\n[[package]]\nname = \"core\"\nversion = \"1.3.0\"\n\n[[package]]\nname = \"core\"\nversion = \"0.9.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n"
+        );
+    }
+}